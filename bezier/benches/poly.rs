@@ -1,4 +1,4 @@
-use bezier::{Poly, SturmSeq};
+use bezier::Poly;
 use criterion::{criterion_group, criterion_main, Criterion};
 
 fn poly_sturm() {
@@ -76,9 +76,17 @@ fn poly_evd() {
     let _ = solve_poly([1.0, -4.0, 2.0, 0.0, -3.0, 7.0]);
 }
 
+fn poly_eval() -> f64 {
+    let poly: Poly = [1.0, -4.0, 2.0, 0.0, -3.0, 7.0].into_iter().collect();
+    (0..64)
+        .map(|i| poly.eval(criterion::black_box(i as f64 / 64.0)))
+        .sum()
+}
+
 fn criterion_benchmark(c: &mut Criterion) {
-    c.bench_function("poly_sturm", |b| b.iter(|| poly_sturm()));
-    c.bench_function("poly_evd", |b| b.iter(|| poly_evd()));
+    c.bench_function("poly_eval", |b| b.iter(poly_eval));
+    c.bench_function("poly_sturm", |b| b.iter(poly_sturm));
+    c.bench_function("poly_evd", |b| b.iter(poly_evd));
 }
 
 criterion_group!(benches, criterion_benchmark);
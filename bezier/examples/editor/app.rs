@@ -10,7 +10,7 @@ use crate::{
     interact::ShapeInteract,
     plot::shape,
     point::Point,
-    window::{ConfigureWindow, FloatWindow, ShapeDataWindow},
+    window::{ConfigureWindow, ExportWindow, FloatWindow, ShapeDataWindow},
 };
 
 pub struct Application {
@@ -27,6 +27,10 @@ impl Application {
         self.id.with("shape-window")
     }
 
+    fn export_window_id(&self) -> Id {
+        self.id.with("export-window")
+    }
+
     fn menu_bar(&self, ui: &mut Ui) {
         ui.menu_button("File", |ui| {
             if ui.button("Import...").clicked() {
@@ -34,7 +38,8 @@ impl Application {
             }
 
             if ui.button("Export...").clicked() {
-                //  TODO
+                ExportWindow::open(ui, self.export_window_id());
+                ui.close_menu();
             }
 
             #[cfg(not(target_arch = "wasm32"))]
@@ -74,6 +79,7 @@ impl App for Application {
         CentralPanel::default().show(ctx, |ui| {
             ConfigureWindow::new(ui, self.configure_window_id()).show(ui, &mut configure::write());
             ShapeDataWindow::new(ui, self.shape_window_id()).show(ui, &mut self.shape);
+            ExportWindow::new(ui, self.export_window_id()).show(ui, &mut self.shape);
 
             if ctx.input(|i| i.key_released(Key::C)) {
                 self.shape.toggle_close();
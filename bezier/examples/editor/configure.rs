@@ -17,7 +17,7 @@ pub struct PointPlotConfig {
 pub struct CurvePlotConfig {
     pub width: f64,
     pub color: Color32,
-    pub samples: usize,
+    pub tolerance: f64,
 }
 
 #[derive(Clone)]
@@ -43,7 +43,7 @@ pub const DEFAULT_CORNEL_POINT_PLOT_CONFIG: CurvePointPlotConfig = CurvePointPlo
     in_handle: CurvePlotConfig {
         width: 1.0,
         color: Color32::DARK_GREEN,
-        samples: 2,
+        tolerance: 0.01,
     },
     out_ctrl: PointPlotConfig {
         mark: MarkerShape::Square,
@@ -53,7 +53,7 @@ pub const DEFAULT_CORNEL_POINT_PLOT_CONFIG: CurvePointPlotConfig = CurvePointPlo
     out_handle: CurvePlotConfig {
         width: 1.0,
         color: Color32::DARK_RED,
-        samples: 2,
+        tolerance: 0.01,
     },
 };
 
@@ -71,7 +71,7 @@ pub const DEFAULT_SMOOTH_POINT_PLOT_CONFIG: CurvePointPlotConfig = CurvePointPlo
     in_handle: CurvePlotConfig {
         width: 1.0,
         color: Color32::DARK_GREEN,
-        samples: 2,
+        tolerance: 0.01,
     },
     out_ctrl: PointPlotConfig {
         mark: MarkerShape::Circle,
@@ -81,20 +81,20 @@ pub const DEFAULT_SMOOTH_POINT_PLOT_CONFIG: CurvePointPlotConfig = CurvePointPlo
     out_handle: CurvePlotConfig {
         width: 1.0,
         color: Color32::DARK_RED,
-        samples: 2,
+        tolerance: 0.01,
     },
 };
 
 pub const DEFAULT_CURVE_SEGMENT_PLOT_CONFIG: CurvePlotConfig = CurvePlotConfig {
     width: 2.0,
     color: Color32::BLUE,
-    samples: 64,
+    tolerance: 0.01,
 };
 
 pub const DEFAULT_CURVE_BEZIER_PLOT_CONFIG: CurvePlotConfig = CurvePlotConfig {
     width: 2.0,
     color: Color32::BLUE,
-    samples: 2,
+    tolerance: 0.01,
 };
 
 #[derive(Clone)]
@@ -116,20 +116,6 @@ impl Default for PlotConfig {
     }
 }
 
-pub struct SubWindowConfig {
-    pub configure: bool,
-    pub shape_data: bool,
-}
-
-impl Default for SubWindowConfig {
-    fn default() -> Self {
-        Self {
-            configure: true,
-            shape_data: false,
-        }
-    }
-}
-
 pub struct ViewConfig {
     pub grid: bool,
     pub point: bool,
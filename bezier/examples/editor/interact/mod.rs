@@ -1,6 +1,6 @@
 use bezier::{CornerPoint, Curve, CurvePoint, Nearest, Point2D, Shape, SmoothPoint};
 use eframe::{
-    egui::{Id, Ui},
+    egui::{Id, Key, Ui},
     epaint::Pos2,
 };
 use egui_plot::{PlotResponse, PlotTransform};
@@ -15,6 +15,9 @@ mod point;
 mod corner;
 mod smooth;
 
+// Max distance from pencil samples to the fitted shape, in plot units.
+const PENCIL_TOLERANCE: f64 = 0.5;
+
 enum PointAction {
     Click,
     Delete,
@@ -59,7 +62,10 @@ impl<'a> ShapeInteract<'a> {
                 }
             }
             PointAction::Delete => {
-                self.shape.remove(index);
+                // keep the outline if possible, endpoints of open shape are simply removed
+                if self.shape.remove_on_curve(index).is_none() {
+                    self.shape.remove(index);
+                }
             }
             PointAction::CornerAddInCtrl => {
                 let points = self.shape.points_mut();
@@ -76,10 +82,16 @@ impl<'a> ShapeInteract<'a> {
                         let ctrl = points[index].point().plus(points[last].point()).scale(0.5);
                         points[index].update_in_ctrl(ctrl);
                     }
+                    Curve::Quad(q) => {
+                        let b = q.to_cubic();
+                        points[last].update_out_ctrl(b.ctrl1);
+                        points[index].update_in_ctrl(b.ctrl2);
+                    }
                     Curve::Bezier(b) => {
                         points[last].update_out_ctrl(b.ctrl1);
                         points[index].update_in_ctrl(b.ctrl2);
                     }
+                    Curve::Arc(_) => unreachable!(),
                 }
             }
             PointAction::CornerAddOutCtrl => {
@@ -97,10 +109,16 @@ impl<'a> ShapeInteract<'a> {
                         let ctrl = points[index].point().plus(points[next].point()).scale(0.5);
                         points[index].update_out_ctrl(ctrl);
                     }
+                    Curve::Quad(q) => {
+                        let b = q.to_cubic();
+                        points[index].update_out_ctrl(b.ctrl1);
+                        points[next].update_in_ctrl(b.ctrl2);
+                    }
                     Curve::Bezier(b) => {
                         points[index].update_out_ctrl(b.ctrl1);
                         points[next].update_in_ctrl(b.ctrl2);
                     }
+                    Curve::Arc(_) => unreachable!(),
                 }
             }
             PointAction::SmoothConvertToCorner => {
@@ -182,6 +200,29 @@ impl<'a> ShapeInteract<'a> {
         }
     }
 
+    // Freehand drawing while holding P, samples are fitted into a new shape when released.
+    fn do_pencil<R>(&mut self, ui: &mut Ui, id: Id, response: &PlotResponse<R>) {
+        let id = id.with("pencil");
+
+        if ui.input(|i| i.key_down(Key::P)) && response.response.dragged() {
+            if let Some(pos) = response.response.interact_pointer_pos() {
+                let p: Point = response.transform.value_from_position(pos).into();
+                ui.data_mut(|d| d.get_temp_mut_or_default::<Vec<Point>>(id).push(p));
+            }
+        }
+
+        if response.response.drag_released() {
+            let samples: Vec<Point> = ui.data_mut(|d| {
+                let samples = d.get_temp(id).unwrap_or_default();
+                d.remove::<Vec<Point>>(id);
+                samples
+            });
+            if samples.len() >= 2 {
+                *self.shape = Shape::fit(&samples, PENCIL_TOLERANCE);
+            }
+        }
+    }
+
     pub fn interact<R>(
         &mut self, ui: &mut Ui, id: Id, response: &PlotResponse<R>, conf: &Configure,
     ) {
@@ -200,6 +241,7 @@ impl<'a> ShapeInteract<'a> {
             self.do_point_action(index, action);
         }
 
+        self.do_pencil(ui, id, response);
         self.do_clicked(response);
     }
 }
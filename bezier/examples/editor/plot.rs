@@ -1,5 +1,6 @@
-use bezier::{Bezier, CornerPoint, Curve, CurvePoint, Point2D, Segment, Shape, SmoothPoint};
-use egui_plot::{Line, PlotPoints, PlotUi, Points};
+use bezier::{Bezier, CornerPoint, Curve, CurvePoint, Segment, Shape, SmoothPoint};
+use eframe::epaint::Color32;
+use egui_plot::{Line, MarkerShape, PlotPoints, PlotUi, Points};
 
 use crate::{
     configure::{
@@ -64,37 +65,43 @@ fn curve_point(p: &CurvePoint<Point>, ui: &mut PlotUi, view: &ViewConfig, opt: &
     }
 }
 
-fn segment(segment: &Segment<Point>, ui: &mut PlotUi, opt: &CurvePlotConfig) {
-    let f = segment.parametric_function();
-    let line = Line::new(PlotPoints::from_parametric_callback(
-        move |t| f(t).tuple(),
-        0.0..=1.0,
-        opt.samples,
-    ))
-    .color(opt.color)
-    .width(opt.width as f32);
+fn polyline(points: Vec<Point>, ui: &mut PlotUi, opt: &CurvePlotConfig) {
+    let line = Line::new(PlotPoints::Owned(points.into_iter().map(|p| p.0).collect()))
+        .color(opt.color)
+        .width(opt.width as f32);
 
     ui.line(line)
 }
 
-fn bezier(bezier: &Bezier<Point>, ui: &mut PlotUi, opt: &CurvePlotConfig) {
-    let f = bezier.parametric_function();
-
-    let line = Line::new(PlotPoints::from_parametric_callback(
-        move |t| f(t).tuple(),
-        0.0..=1.0,
-        opt.samples,
-    ))
-    .color(opt.color)
-    .width(opt.width as f32);
+fn segment(segment: &Segment<Point>, ui: &mut PlotUi, opt: &CurvePlotConfig) {
+    polyline(segment.flatten(), ui, opt)
+}
 
-    ui.line(line);
+fn bezier(bezier: &Bezier<Point>, ui: &mut PlotUi, opt: &CurvePlotConfig) {
+    polyline(bezier.flatten(opt.tolerance), ui, opt);
+
+    // warn about curve crossing itself
+    if let Some((t, _)) = bezier.self_intersection() {
+        ui.points(
+            Points::new(PlotPoints::Owned(vec![bezier.at(t).0]))
+                .shape(MarkerShape::Cross)
+                .radius(6.0)
+                .color(Color32::RED)
+                .name("Self intersection"),
+        )
+    }
 }
 
 fn curve(c: &Curve<Point>, ui: &mut PlotUi, opt: &PlotConfig) {
     match c {
         Curve::Segment(s) => segment(s, ui, &opt.segment),
+        Curve::Quad(q) => bezier(&q.to_cubic(), ui, &opt.bezier),
         Curve::Bezier(b) => bezier(b, ui, &opt.bezier),
+        Curve::Arc(a) => {
+            for b in a.to_cubics(opt.bezier.tolerance) {
+                bezier(&b, ui, &opt.bezier);
+            }
+        }
     }
 }
 
@@ -98,7 +98,7 @@ impl ConfigureWindow {
         ui.vertical_centered(|ui| {
             ui.color_edit_button_srgba(&mut conf.color);
         });
-        ui.add(Slider::new(&mut conf.samples, 2..=256));
+        ui.add(Slider::new(&mut conf.tolerance, 0.001..=1.0).logarithmic(true));
         ui.end_row();
     }
 
@@ -141,7 +141,7 @@ impl ConfigureWindow {
                     ui.label("Color");
                 });
                 ui.vertical_centered(|ui| {
-                    ui.label("Samples");
+                    ui.label("Tolerance");
                 });
                 ui.end_row();
                 content(ui);
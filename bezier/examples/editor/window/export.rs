@@ -0,0 +1,32 @@
+use bezier::{Shape, SvgPathFormat};
+use eframe::egui::{Slider, TextEdit, Ui};
+
+use crate::point::Point;
+
+impl_window! {
+    ExportWindow<Shape<Point>> as "Export" : ExportWindowState {
+        precision: usize = 3,
+        relative: bool = false,
+    }
+}
+
+impl ExportWindow {
+    fn controls(&mut self, ui: &mut Ui, data: &mut Shape<Point>) {
+        ui.horizontal(|ui| {
+            ui.label("Precision: ");
+            ui.add(Slider::new(&mut self.state.precision, 0..=8));
+        });
+        ui.checkbox(&mut self.state.relative, "Relative command");
+
+        let format = SvgPathFormat::new()
+            .with_precision(self.state.precision)
+            .with_relative(self.state.relative);
+        let path = data.to_svg_path(&format);
+
+        if ui.button("Copy").clicked() {
+            ui.output_mut(|o| o.copied_text = path.clone());
+        }
+
+        ui.add(TextEdit::multiline(&mut path.as_str()).desired_width(f32::INFINITY));
+    }
+}
@@ -32,6 +32,7 @@ pub trait FloatWindow {
         Self::new(ui, id).update(ui, |_, s| *s.open_mut() = true);
     }
 
+    #[allow(dead_code)]
     fn close(ui: &mut Ui, id: Id)
     where
         Self: Sized,
@@ -150,6 +151,7 @@ macro_rules! impl_window {
 }
 
 mod configure;
+mod export;
 mod shape;
 
-pub use self::{configure::ConfigureWindow, shape::ShapeDataWindow};
+pub use self::{configure::ConfigureWindow, export::ExportWindow, shape::ShapeDataWindow};
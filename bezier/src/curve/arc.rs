@@ -0,0 +1,613 @@
+use alloc::vec::Vec;
+use core::f64::consts::{FRAC_PI_2, PI, TAU};
+
+use super::{length, Bezier, Conic, Nearest};
+use crate::{integrate, Point2D, Poly, Rect, Root};
+
+// Min tolerance of `to_cubics` relative to the larger radius, smaller ones are raised to it.
+const MIN_RELATIVE_TOLERANCE: f64 = 1e-9;
+
+// Max count of cubic curves an arc is approximated with, which is never reached for a sweep
+// angle within a few turns.
+const MAX_CUBICS: usize = 1 << 10;
+
+/// Elliptical arc, which is a part of the ellipse centered at `center` with radii `radii`, and
+/// whose x axis is rotated by `rotation` degrees.
+///
+/// The arc starts at angle `start_angle` and goes `sweep_angle` degrees, positive sweep goes
+/// counterclockwise. Angles are measured on the ellipse before rotation, that is the point at
+/// angle θ is `(rx cos θ, ry sin θ)` rotated and moved to center.
+///
+/// Parameter t in [0, 1] maps to angle `start_angle + t * sweep_angle` linearly.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Arc<P> {
+    pub center: P,
+    pub radii: P,
+    pub rotation: f64,
+    pub start_angle: f64,
+    pub sweep_angle: f64,
+}
+
+/// Shape of an elliptical arc between two given endpoints, which is the arguments of SVG `A`
+/// command except the end point.
+///
+/// Unlike [Arc], it doesn't store the position, so the arc follows its endpoints when they move.
+/// See [Arc::from_endpoints] for how it's resolved.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArcParams<P> {
+    pub radii: P,
+    pub rotation: f64,
+    pub large_arc: bool,
+    pub sweep: bool,
+}
+
+impl<P> ArcParams<P> {
+    pub fn new(radii: P, rotation: f64, large_arc: bool, sweep: bool) -> Self {
+        Self {
+            radii,
+            rotation,
+            large_arc,
+            sweep,
+        }
+    }
+}
+
+impl<P: Point2D> ArcParams<P> {
+    /// The arc from `from` to `to`, `None` if it should be a line. See [Arc::from_endpoints].
+    pub fn to_arc(&self, from: &P, to: &P) -> Option<Arc<P>> {
+        Arc::from_endpoints(
+            from,
+            &self.radii,
+            self.rotation,
+            self.large_arc,
+            self.sweep,
+            to,
+        )
+    }
+}
+
+impl<P> Arc<P> {
+    pub fn new(center: P, radii: P, rotation: f64, start_angle: f64, sweep_angle: f64) -> Self {
+        Self {
+            center,
+            radii,
+            rotation,
+            start_angle,
+            sweep_angle,
+        }
+    }
+}
+
+impl<P: Point2D> Arc<P> {
+    /// Arc from SVG endpoint parameterization, which is the arguments of SVG `A` command.
+    ///
+    /// Radii are scaled up if they are too small to reach `to`. Returns `None` if the arc should
+    /// be a line, that is either radius is zero, or `from` and `to` are the same point.
+    ///
+    /// See: <https://www.w3.org/TR/SVG/implnote.html#ArcImplementationNotes>
+    pub fn from_endpoints(
+        from: &P, radii: &P, rotation: f64, large_arc: bool, sweep: bool, to: &P,
+    ) -> Option<Self> {
+        let mut rx = libm::fabs(radii.x());
+        let mut ry = libm::fabs(radii.y());
+
+        if rx == 0.0 || ry == 0.0 || from.distance(to) == 0.0 {
+            return None;
+        }
+
+        let (sin, cos) = libm::sincos(rotation.to_radians());
+
+        // step 1: compute (x1', y1')
+        let half = from.minus(to).scale(0.5);
+        let x1 = cos * half.x() + sin * half.y();
+        let y1 = -sin * half.x() + cos * half.y();
+
+        // ensure radii are large enough
+        let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+        if lambda > 1.0 {
+            let s = libm::sqrt(lambda);
+            rx *= s;
+            ry *= s;
+        }
+
+        // step 2: compute (cx', cy')
+        let num = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+        let den = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+        let sign = if large_arc == sweep { -1.0 } else { 1.0 };
+        let coef = sign * libm::sqrt(f64::max(0.0, num / den));
+        let cx1 = coef * rx * y1 / ry;
+        let cy1 = -coef * ry * x1 / rx;
+
+        // step 3: compute (cx, cy)
+        let mid = from.plus(to).scale(0.5);
+        let cx = cos * cx1 - sin * cy1 + mid.x();
+        let cy = sin * cx1 + cos * cy1 + mid.y();
+
+        // step 4: compute start angle and sweep angle
+        let (ux, uy) = ((x1 - cx1) / rx, (y1 - cy1) / ry);
+        let (vx, vy) = ((-x1 - cx1) / rx, (-y1 - cy1) / ry);
+        let start = libm::atan2(uy, ux);
+        let mut delta = libm::atan2(ux * vy - uy * vx, ux * vx + uy * vy);
+        if !sweep && delta > 0.0 {
+            delta -= TAU;
+        } else if sweep && delta < 0.0 {
+            delta += TAU;
+        }
+
+        Some(Self::new(
+            P::from_xy(cx, cy),
+            P::from_xy(rx, ry),
+            rotation,
+            start.to_degrees(),
+            delta.to_degrees(),
+        ))
+    }
+
+    /// Endpoint parameterization of the arc, which is the inverse of [Arc::from_endpoints] with
+    /// [Arc::start] and [Arc::end].
+    ///
+    /// Endpoints of an arc sweeping 360 degrees or more are the same point, which can't be
+    /// resolved back, split it first.
+    pub fn params(&self) -> ArcParams<P> {
+        ArcParams::new(
+            P::from_xy(libm::fabs(self.radii.x()), libm::fabs(self.radii.y())),
+            self.rotation,
+            libm::fabs(self.sweep_angle) > 180.0,
+            self.sweep_angle > 0.0,
+        )
+    }
+
+    // (sin, cos) of rotation
+    fn rotation_sincos(&self) -> (f64, f64) {
+        libm::sincos(self.rotation.to_radians())
+    }
+
+    // Angle in radians at t.
+    fn angle_at(&self, t: f64) -> f64 {
+        (self.start_angle + self.sweep_angle * t).to_radians()
+    }
+
+    // Map a vector on the unit circle to the ellipse, without moving to center.
+    fn map_vector(&self, x: f64, y: f64) -> P {
+        let (sin, cos) = self.rotation_sincos();
+        let (rx, ry) = (self.radii.x(), self.radii.y());
+
+        P::from_xy(rx * cos * x - ry * sin * y, rx * sin * x + ry * cos * y)
+    }
+
+    // Map a point on the unit circle to the ellipse.
+    fn map(&self, x: f64, y: f64) -> P {
+        self.center.plus(&self.map_vector(x, y))
+    }
+
+    // Parameter of angle θ in radians, if it's on the arc.
+    fn t_of_angle(&self, theta: f64) -> Option<f64> {
+        let sweep = self.sweep_angle.to_radians();
+        if sweep == 0.0 {
+            return None;
+        }
+
+        let start = self.start_angle.to_radians();
+        let d = if sweep > 0.0 {
+            theta - start
+        } else {
+            start - theta
+        };
+
+        let mut d = libm::fmod(d, TAU);
+        if d < 0.0 {
+            d += TAU;
+        }
+
+        let t = d / libm::fabs(sweep);
+        (t <= 1.0).then_some(t)
+    }
+
+    pub fn parametric_function(&self) -> impl Fn(f64) -> P + '_ {
+        move |t| {
+            let (sin, cos) = libm::sincos(self.angle_at(t));
+            self.map(cos, sin)
+        }
+    }
+
+    pub fn at(&self, t: f64) -> P {
+        assert!((0.0..=1.0).contains(&t));
+        self.parametric_function()(t)
+    }
+
+    /// Start point of the arc.
+    pub fn start(&self) -> P {
+        self.at(0.0)
+    }
+
+    /// End point of the arc.
+    pub fn end(&self) -> P {
+        self.at(1.0)
+    }
+
+    /// First derivative B'(t).
+    pub fn derivative(&self, t: f64) -> P {
+        let (sin, cos) = libm::sincos(self.angle_at(t));
+
+        self.map_vector(-sin, cos)
+            .scale(self.sweep_angle.to_radians())
+    }
+
+    /// Second derivative B''(t).
+    pub fn second_derivative(&self, t: f64) -> P {
+        let (sin, cos) = libm::sincos(self.angle_at(t));
+        let sweep = self.sweep_angle.to_radians();
+
+        self.map_vector(-cos, -sin).scale(sweep * sweep)
+    }
+
+    /// Unit tangent vector at t.
+    pub fn tangent(&self, t: f64) -> P {
+        self.derivative(t).normalize()
+    }
+
+    /// Unit normal vector at t, which is the tangent rotated 90 degree counterclockwise.
+    pub fn normal(&self, t: f64) -> P {
+        let tangent = self.tangent(t);
+
+        P::from_xy(-tangent.y(), tangent.x())
+    }
+
+    /// Signed curvature at t, positive when the arc turns counterclockwise.
+    pub fn curvature(&self, t: f64) -> f64 {
+        let d1 = self.derivative(t);
+        let d2 = self.second_derivative(t);
+        let speed = d1.length_from_origin();
+
+        d1.cross(&d2) / (speed * speed * speed)
+    }
+
+    /// Signed radius of curvature at t, which is reciprocal of [Arc::curvature].
+    pub fn radius_of_curvature(&self, t: f64) -> f64 {
+        1.0 / self.curvature(t)
+    }
+
+    pub fn split_at(&self, t: f64) -> (Self, Self) {
+        let mid = self.sweep_angle * t;
+
+        let left = Self::new(
+            self.center.clone(),
+            self.radii.clone(),
+            self.rotation,
+            self.start_angle,
+            mid,
+        );
+        let right = Self::new(
+            self.center.clone(),
+            self.radii.clone(),
+            self.rotation,
+            self.start_angle + mid,
+            self.sweep_angle - mid,
+        );
+
+        (left, right)
+    }
+
+    // Angles where the distance to target reaches local extremum.
+    //
+    // With target (x, y) in the ellipse's own frame, the derivative of squared distance is zero
+    // when:
+    //
+    // ```text
+    // (ry^2 - rx^2) sin θ cos θ + x rx sin θ - y ry cos θ = 0
+    // ```
+    //
+    // Substituting u = tan(θ/2) makes it a quartic polynomial of u. Its leading coefficient
+    // vanishes as the target gets close to the x axis, which makes roots near 0 hard to isolate in
+    // the range bounded by coefficients. So roots are only solved in [-1, 1], and the other half
+    // of the ellipse is solved with w = 1/u, whose quartic has the coefficients reversed.
+    fn distance_critical_angles(&self, target: &P) -> Vec<f64> {
+        let (sin, cos) = self.rotation_sincos();
+        let v = target.minus(&self.center);
+        let x = cos * v.x() + sin * v.y();
+        let y = -sin * v.x() + cos * v.y();
+        let (rx, ry) = (self.radii.x(), self.radii.y());
+
+        let k = rx * rx - ry * ry;
+        let c = [y * ry, 2.0 * (k + x * rx), 0.0, 2.0 * (x * rx - k), -y * ry];
+
+        let roots = |c: [f64; 5]| c.into_iter().collect::<Poly>().real_roots_in(-1.0..=1.0);
+        let mut reversed = c;
+        reversed.reverse();
+
+        match (roots(c), roots(reversed)) {
+            // target is at center of a circle, all points have same distance
+            (Root::Any, _) | (_, Root::Any) => vec![self.angle_at(0.5)],
+            (u, w) => {
+                let u = match u {
+                    Root::Roots(roots) => roots,
+                    _ => Vec::new(),
+                };
+                let w = match w {
+                    Root::Roots(roots) => roots,
+                    _ => Vec::new(),
+                };
+
+                u.into_iter()
+                    .map(|u| 2.0 * libm::atan(u))
+                    .chain(w.into_iter().map(|w| 2.0 * libm::atan2(1.0, w)))
+                    .collect()
+            }
+        }
+    }
+
+    pub fn nearest_to(&self, target: &P, allow_endpoint: bool) -> Option<Nearest<P>> {
+        let endpoints = if allow_endpoint {
+            Some(0.0).into_iter().chain(Some(1.0))
+        } else {
+            None.into_iter().chain(None)
+        };
+
+        self.distance_critical_angles(target)
+            .into_iter()
+            .filter_map(|theta| self.t_of_angle(theta))
+            .filter(|t| 0.0 < *t && *t < 1.0)
+            .chain(endpoints)
+            .map(|t| Nearest::new_from_arc(self, t, target))
+            .min()
+    }
+
+    /// Sorted t values in [0, 1] where x'(t) or y'(t) is zero.
+    pub fn extrema(&self) -> Vec<f64> {
+        let (sin, cos) = self.rotation_sincos();
+        let (rx, ry) = (self.radii.x(), self.radii.y());
+
+        let x = libm::atan2(-ry * sin, rx * cos);
+        let y = libm::atan2(ry * cos, rx * sin);
+
+        let mut roots: Vec<_> = [x, x + PI, y, y + PI]
+            .into_iter()
+            .filter_map(|theta| self.t_of_angle(theta))
+            .collect();
+        roots.sort_by(f64::total_cmp);
+        roots.dedup();
+
+        roots
+    }
+
+    /// Exact bounding box of the arc.
+    pub fn bounds(&self) -> Rect<P> {
+        let mut rect = Rect::new(self.start(), self.end());
+
+        let f = self.parametric_function();
+        for t in self.extrema() {
+            rect.include(&f(t));
+        }
+
+        rect
+    }
+
+    fn speed_function(&self) -> impl Fn(f64) -> f64 + '_ {
+        move |t| self.derivative(t).length_from_origin()
+    }
+
+    /// Arc length of the arc, `tolerance` is the max error allowed.
+    pub fn length(&self, tolerance: f64) -> f64 {
+        integrate(self.speed_function(), 0.0, 1.0, tolerance)
+    }
+
+    /// Find t where the arc length from start point is `s`, clamped to [0, 1].
+    ///
+    /// The arc length of result t differs from `s` by at most `tolerance`.
+    pub fn t_at_length(&self, s: f64, tolerance: f64) -> f64 {
+        let speed = self.speed_function();
+        let total = integrate(&speed, 0.0, 1.0, tolerance / 4.0);
+
+        length::t_at_length(
+            s,
+            total,
+            tolerance,
+            |t| integrate(&speed, 0.0, t, tolerance / 4.0),
+            &speed,
+        )
+    }
+
+    // The larger one of the two radii.
+    pub(crate) fn max_radius(&self) -> f64 {
+        f64::max(libm::fabs(self.radii.x()), libm::fabs(self.radii.y()))
+    }
+
+    // Max distance between the arc and the cubic curve `to_cubics` makes for a piece of `step`
+    // radians, which is 2 sin^6(step/4) / (27 cos^2(step/4)) on the unit circle.
+    fn cubic_error(&self, step: f64) -> f64 {
+        let (sin, cos) = libm::sincos(libm::fabs(step) / 4.0);
+
+        self.max_radius() * 2.0 * libm::pow(sin, 6.0) / (27.0 * cos * cos)
+    }
+
+    /// Approximate the arc with cubic bezier curves, the distance between them is within
+    /// `tolerance`.
+    ///
+    /// Each curve covers at most 90 degrees, whose error is about 2.7e-4 of the larger radius, and
+    /// more curves are used for a smaller `tolerance`. `tolerance` is at least 1e-9 of the larger
+    /// radius, smaller or non-positive ones are raised to it.
+    ///
+    /// Curves split the sweep angle evenly, the first one starts at [Arc::start] and the last one
+    /// ends at [Arc::end].
+    pub fn to_cubics(&self, tolerance: f64) -> Vec<Bezier<P>> {
+        let tolerance = f64::max(tolerance, self.max_radius() * MIN_RELATIVE_TOLERANCE);
+        let delta = self.sweep_angle.to_radians();
+        let min = libm::ceil(libm::fabs(delta) / FRAC_PI_2 - 1e-9).max(1.0) as usize;
+        let n = (min..MAX_CUBICS)
+            .find(|n| self.cubic_error(delta / *n as f64) <= tolerance)
+            .unwrap_or(MAX_CUBICS);
+        let step = delta / n as f64;
+        let k = 4.0 / 3.0 * libm::tan(step / 4.0);
+
+        (0..n)
+            .map(|i| {
+                let a1 = self.start_angle.to_radians() + step * i as f64;
+                let a2 = a1 + step;
+                let (s1, c1) = libm::sincos(a1);
+                let (s2, c2) = libm::sincos(a2);
+
+                Bezier::new(
+                    self.map(c1, s1),
+                    self.map(c1 - k * s1, s1 + k * c1),
+                    self.map(c2 + k * s2, s2 - k * c2),
+                    self.map(c2, s2),
+                )
+            })
+            .collect()
+    }
+
+    /// The arc as rational quadratic curves, which is exact, each one covers at most 90 degrees.
+    pub fn to_conics(&self) -> Vec<Conic<P>> {
+        let delta = self.sweep_angle.to_radians();
+        let n = libm::ceil(libm::fabs(delta) / FRAC_PI_2 - 1e-9).max(1.0) as usize;
+        let step = delta / n as f64;
+        let weight = libm::cos(step / 2.0);
+
+        (0..n)
+            .map(|i| {
+                let a1 = self.start_angle.to_radians() + step * i as f64;
+                let a2 = a1 + step;
+                let (s1, c1) = libm::sincos(a1);
+                let (s2, c2) = libm::sincos(a2);
+                // tangents at both ends meet at the middle angle
+                let (sm, cm) = libm::sincos(a1 + step / 2.0);
+
+                Conic::new(
+                    self.map(c1, s1),
+                    self.map(cm / weight, sm / weight),
+                    self.map(c2, s2),
+                    weight,
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::f64::consts::FRAC_1_SQRT_2;
+
+    use super::*;
+
+    #[test]
+    fn arc_from_endpoints() {
+        let arc = Arc::from_endpoints(&(10.0, 0.0), &(10.0, 10.0), 0.0, false, true, &(0.0, 10.0))
+            .unwrap();
+        assert!(arc.center.distance(&(0.0, 0.0)) < 1e-12);
+        assert!((arc.sweep_angle - 90.0).abs() < 1e-12);
+        assert!(
+            arc.at(0.5)
+                .distance(&(10.0 * FRAC_1_SQRT_2, 10.0 * FRAC_1_SQRT_2))
+                < 1e-12
+        );
+        assert!((arc.curvature(0.3) - 0.1).abs() < 1e-12);
+        assert!((arc.length(1e-9) - 5.0 * PI).abs() < 1e-9);
+
+        assert!(
+            Arc::from_endpoints(&(0.0, 0.0), &(0.0, 1.0), 0.0, false, true, &(1.0, 0.0)).is_none()
+        );
+    }
+
+    #[test]
+    fn arc_params_round_trip() {
+        for arc in [
+            Arc::new((1.0, 2.0), (20.0, 10.0), 30.0, 200.0, -300.0),
+            Arc::new((0.0, 0.0), (5.0, 5.0), 0.0, 10.0, 90.0),
+        ] {
+            let resolved = arc.params().to_arc(&arc.start(), &arc.end()).unwrap();
+            assert!(resolved.center.distance(&arc.center) < 1e-9);
+            assert!((resolved.sweep_angle - arc.sweep_angle).abs() < 1e-9);
+            assert!(resolved.at(0.3).distance(&arc.at(0.3)) < 1e-9);
+        }
+    }
+
+    #[test]
+    fn arc_split_and_bounds() {
+        // rotated ellipse, clockwise
+        let arc = Arc::new((1.0, 2.0), (20.0, 10.0), 30.0, 200.0, -300.0);
+
+        let (left, right) = arc.split_at(0.4);
+        assert!(left.at(0.5).distance(&arc.at(0.2)) < 1e-12);
+        assert!(right.at(0.5).distance(&arc.at(0.7)) < 1e-12);
+
+        let bounds = arc.bounds();
+        let mut sampled = Rect::from_point(arc.start());
+        for i in 0..=10000 {
+            let p = arc.at(i as f64 / 10000.0);
+            assert!(bounds.distance_to(&p) < 1e-9);
+            sampled.include(&p);
+        }
+        assert!((bounds.width() - sampled.width()).abs() < 1e-3);
+        assert!((bounds.height() - sampled.height()).abs() < 1e-3);
+
+        let cubics = arc.to_cubics(1.0);
+        assert_eq!(cubics.len(), 4);
+        assert!(cubics[0].start.distance(&arc.start()) < 1e-12);
+        assert!(cubics[3].end.distance(&arc.end()) < 1e-12);
+
+        let conics = arc.to_conics();
+        assert_eq!(conics.len(), 4);
+        assert!(conics[3].end.distance(&arc.end()) < 1e-12);
+        for c in &conics {
+            let d = arc.nearest_to(&c.at(0.3), true).unwrap().distance;
+            assert!(d < 1e-9, "{d}");
+        }
+    }
+
+    #[test]
+    fn arc_to_cubics_within_tolerance() {
+        let arc = Arc::new((1.0, 2.0), (200.0, 100.0), 30.0, 200.0, -300.0);
+
+        let mut last = 0;
+        for tolerance in [1.0, 1e-2, 1e-4, 1e-6] {
+            let cubics = arc.to_cubics(tolerance);
+            assert!(cubics.len() > last);
+            last = cubics.len();
+
+            for b in &cubics {
+                for i in 0..=32 {
+                    let d = arc
+                        .nearest_to(&b.at(i as f64 / 32.0), true)
+                        .unwrap()
+                        .distance;
+                    assert!(d <= tolerance, "{d} > {tolerance}");
+                }
+            }
+        }
+
+        let min = arc.to_cubics(200.0 * MIN_RELATIVE_TOLERANCE);
+        for tolerance in [0.0, -1.0, f64::NAN] {
+            assert_eq!(arc.to_cubics(tolerance), min);
+        }
+    }
+
+    #[test]
+    fn arc_nearest_on_axis() {
+        let arc = Arc::new((1.0, 2.0), (200.0, 100.0), 30.0, 200.0, -300.0);
+
+        // vertices of the ellipse, and a point off the x axis by floating error in its own frame
+        let targets = [(1.0, 0.0), (-1.0, 0.0), (0.0, 1.0), (0.0, -1.0)]
+            .map(|(x, y)| arc.map(x, y))
+            .into_iter()
+            .chain(Some((174.20508075688778, 101.99999999999996)));
+        for target in targets {
+            let nearest = arc.nearest_to(&target, true).unwrap();
+            assert!(nearest.distance < 1e-9, "{}", nearest.distance);
+        }
+    }
+
+    #[test]
+    fn arc_nearest() {
+        let arc = Arc::new((0.0, 0.0), (20.0, 10.0), 30.0, -90.0, 270.0);
+
+        for target in [(5.0, 3.0), (-30.0, 12.0), (0.0, -40.0), (0.0, 0.0)] {
+            let nearest = arc.nearest_to(&target, true).unwrap();
+
+            let brute = (0..=10000)
+                .map(|i| arc.at(i as f64 / 10000.0).distance(&target))
+                .fold(f64::INFINITY, f64::min);
+            assert!(nearest.distance <= brute + 1e-9);
+            assert!(nearest.distance > brute - 1e-3);
+        }
+    }
+}
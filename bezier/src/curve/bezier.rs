@@ -1,12 +1,13 @@
-use alloc::vec;
+use alloc::{vec, vec::Vec};
 
 use dyn_stack::PodStack;
 use faer_core::{Mat, Parallelism};
 use faer_evd::{ComputeVectors, EvdParams};
 
-use super::Nearest;
-use crate::Point2D;
+use super::{length, Nearest};
+use crate::{integrate, Point2D, Poly, Rect, Root};
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct Bezier<P> {
     pub start: P,
     pub ctrl1: P,
@@ -37,7 +38,7 @@ impl<P: Point2D> Bezier<P> {
         Self::new(start, ctrl1, ctrl2, end)
     }
 
-    fn parametric_function_coefficients(&self) -> [P; 4] {
+    pub(crate) fn parametric_function_coefficients(&self) -> [P; 4] {
         [
             self.start
                 .negative()
@@ -68,6 +69,74 @@ impl<P: Point2D> Bezier<P> {
         self.parametric_function()(t)
     }
 
+    fn derivative_coefficients(&self) -> [P; 3] {
+        let [a, b, c, _] = self.parametric_function_coefficients();
+
+        [a.scale(3.0), b.scale(2.0), c]
+    }
+
+    /// First derivative B'(t).
+    pub fn derivative(&self, t: f64) -> P {
+        let [a, b, c] = self.derivative_coefficients();
+
+        a.scale(t * t).plus(&b.scale(t)).plus(&c)
+    }
+
+    /// Second derivative B''(t).
+    pub fn second_derivative(&self, t: f64) -> P {
+        let [a, b, _] = self.derivative_coefficients();
+
+        a.scale(2.0 * t).plus(&b)
+    }
+
+    /// Unit tangent vector at t.
+    ///
+    /// Where the first derivative is zero, e.g. control point coincides with endpoint, or at a
+    /// cusp, direction of the higher order derivative is used.
+    pub fn tangent(&self, t: f64) -> P {
+        let [a, ..] = self.parametric_function_coefficients();
+
+        [self.derivative(t), self.second_derivative(t)]
+            .into_iter()
+            .find(|d| d.length_from_origin() != 0.0)
+            .unwrap_or(a)
+            .normalize()
+    }
+
+    /// Unit normal vector at t, which is the tangent rotated 90 degree counterclockwise.
+    pub fn normal(&self, t: f64) -> P {
+        let tangent = self.tangent(t);
+
+        P::from_xy(-tangent.y(), tangent.x())
+    }
+
+    /// Signed curvature at t, positive when the curve turns counterclockwise.
+    ///
+    /// Not finite where the first derivative is zero.
+    pub fn curvature(&self, t: f64) -> f64 {
+        let d1 = self.derivative(t);
+        let d2 = self.second_derivative(t);
+        let speed = d1.length_from_origin();
+
+        d1.cross(&d2) / (speed * speed * speed)
+    }
+
+    /// Signed radius of curvature at t, which is reciprocal of [Bezier::curvature].
+    pub fn radius_of_curvature(&self, t: f64) -> f64 {
+        1.0 / self.curvature(t)
+    }
+
+    fn speed_function(&self) -> impl Fn(f64) -> f64 {
+        let [a, b, c] = self.derivative_coefficients();
+
+        move |t| {
+            a.scale(t * t)
+                .plus(&b.scale(t))
+                .plus(&c)
+                .length_from_origin()
+        }
+    }
+
     fn distance_derivative_coefficients(&self, target: &P) -> [f64; 6] {
         let [a, b, c, d] = self.parametric_function_coefficients();
 
@@ -187,6 +256,87 @@ impl<P: Point2D> Bezier<P> {
         (left, right)
     }
 
+    /// Arc length of the curve, `tolerance` is the max error allowed.
+    pub fn length(&self, tolerance: f64) -> f64 {
+        integrate(self.speed_function(), 0.0, 1.0, tolerance)
+    }
+
+    /// Find t where the arc length from start point is `s`, clamped to [0, 1].
+    ///
+    /// The arc length of result t differs from `s` by at most `tolerance`.
+    pub fn t_at_length(&self, s: f64, tolerance: f64) -> f64 {
+        let speed = self.speed_function();
+        let total = integrate(&speed, 0.0, 1.0, tolerance / 4.0);
+
+        length::t_at_length(
+            s,
+            total,
+            tolerance,
+            |t| integrate(&speed, 0.0, t, tolerance / 4.0),
+            &speed,
+        )
+    }
+
+    /// Bounding box of the four control points, which always contains the curve.
+    pub fn control_bounds(&self) -> Rect<P> {
+        Rect::from_points([&self.start, &self.ctrl1, &self.ctrl2, &self.end]).unwrap()
+    }
+
+    // sorted roots in [0, 1], empty if polynomial is zero
+    fn roots_in_unit(poly: Poly) -> Vec<f64> {
+        let mut roots = match poly.real_roots_in(0.0..=1.0) {
+            Root::Roots(roots) => roots,
+            Root::None | Root::Any => Vec::new(),
+        };
+        roots.sort_by(f64::total_cmp);
+        roots
+    }
+
+    /// Sorted t values in [0, 1] where x'(t) or y'(t) is zero.
+    pub fn extrema(&self) -> Vec<f64> {
+        let [a, b, c] = self.derivative_coefficients();
+
+        let x: Poly = [a.x(), b.x(), c.x()].into_iter().collect();
+        let y: Poly = [a.y(), b.y(), c.y()].into_iter().collect();
+
+        let mut roots: Vec<_> = [x, y].into_iter().flat_map(Self::roots_in_unit).collect();
+        roots.sort_by(f64::total_cmp);
+        roots.dedup();
+
+        roots
+    }
+
+    /// Sorted t values in [0, 1] where the curve changes its turning direction.
+    ///
+    /// They are roots of B'(t) x B''(t), which is a quadratic polynomial:
+    ///
+    /// ```text
+    /// -3(a x b)t^2 + 3(c x a)t + (c x b)
+    /// ```
+    ///
+    /// where a, b, c are coefficients of t^3, t^2, t of the curve.
+    pub fn inflections(&self) -> Vec<f64> {
+        let [a, b, c, _] = self.parametric_function_coefficients();
+
+        let poly: Poly = [-3.0 * a.cross(&b), 3.0 * c.cross(&a), c.cross(&b)]
+            .into_iter()
+            .collect();
+
+        Self::roots_in_unit(poly)
+    }
+
+    /// Exact bounding box of the curve.
+    pub fn bounds(&self) -> Rect<P> {
+        let mut rect = Rect::new(self.start.clone(), self.end.clone());
+
+        let f = self.parametric_function();
+        for t in self.extrema() {
+            rect.include(&f(t));
+        }
+
+        rect
+    }
+
     /// Calculate the nearest point on the segment to a provided target point.
     pub fn nearest_to(&self, target: &P, allow_endpoint: bool) -> Option<Nearest<P>> {
         let coefficients = self.distance_derivative_coefficients(target);
@@ -218,3 +368,89 @@ impl<P: Point2D> Bezier<P> {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn bezier_bounds() {
+        let b = Bezier::new((0.0, 0.0), (0.0, 10.0), (30.0, 10.0), (30.0, 0.0));
+        let rect = b.bounds();
+        assert_eq!(*rect.min(), (0.0, 0.0));
+        assert!((rect.max().0 - 30.0).abs() < 1e-12);
+        assert!((rect.max().1 - 7.5).abs() < 1e-12);
+
+        let rect = b.control_bounds();
+        assert_eq!(*rect.max(), (30.0, 10.0));
+    }
+
+    #[test]
+    fn bezier_length_of_line() {
+        let b = Bezier::new((0.0, 0.0), (1.0, 0.0), (2.0, 0.0), (3.0, 0.0));
+        assert!((b.length(1e-9) - 3.0).abs() < 1e-9);
+        assert!((b.t_at_length(1.5, 1e-9) - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn bezier_t_at_length() {
+        let b = Bezier::new((0.0, 0.0), (0.0, 10.0), (30.0, 10.0), (30.0, 0.0));
+        let total = b.length(1e-9);
+        for i in 0..=10 {
+            let s = total * i as f64 / 10.0;
+            let t = b.t_at_length(s, 1e-9);
+            let (left, _) = b.split_at(t);
+            assert!((left.length(1e-10) - s).abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn bezier_curvature_of_arc() {
+        // quarter of unit circle approximation, counterclockwise, curvature differs a little
+        let k = 0.552_284_749_831;
+        let b = Bezier::new((1.0, 0.0), (1.0, k), (k, 1.0), (0.0, 1.0));
+
+        for t in [0.0, 0.25, 0.5, 0.75, 1.0] {
+            assert!((b.curvature(t) - 1.0).abs() < 0.05, "{}", b.curvature(t));
+            assert!((b.radius_of_curvature(t) - 1.0).abs() < 0.05);
+
+            // normal of counterclockwise arc points to the center
+            let n = b.normal(t);
+            assert!(b.at(t).plus(&n).length_from_origin() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn bezier_tangent_degenerated() {
+        let b = Bezier::new((0.0, 0.0), (0.0, 0.0), (10.0, 10.0), (10.0, 0.0));
+        assert_eq!(b.derivative(0.0), (0.0, 0.0));
+
+        let tangent = b.tangent(0.0);
+        assert!((tangent.x() - tangent.y()).abs() < 1e-12);
+        assert!((tangent.length_from_origin() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn bezier_extrema() {
+        let b = Bezier::new((0.0, 0.0), (0.0, 10.0), (30.0, 10.0), (30.0, 0.0));
+        let extrema = b.extrema();
+        // x' is zero at both endpoints
+        assert_eq!(extrema.len(), 3);
+        assert!((extrema[1] - 0.5).abs() < 1e-9);
+
+        let b = Bezier::new((0.0, 0.0), (10.0, 10.0), (10.0, 10.0), (0.0, 0.0));
+        assert!((b.extrema()[0] - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn bezier_inflections() {
+        let b = Bezier::new((0.0, 0.0), (1.0, 2.0), (2.0, -2.0), (3.0, 0.0));
+        let inflections = b.inflections();
+        assert_eq!(inflections.len(), 1);
+        assert!((inflections[0] - 0.5).abs() < 1e-9);
+        assert!(b.curvature(inflections[0]).abs() < 1e-9);
+
+        let b = Bezier::new((0.0, 0.0), (0.0, 10.0), (30.0, 10.0), (30.0, 0.0));
+        assert!(b.inflections().is_empty());
+    }
+}
@@ -0,0 +1,146 @@
+use super::Bezier;
+use crate::Point2D;
+
+// Relative tolerance used when classifying, scaled by size of the curve.
+const CLASSIFY_EPSILON: f64 = 1e-9;
+
+/// Kind of a cubic bezier curve, see [Bezier::classify].
+///
+/// Parameters in [CubicKind::Cusp] and [CubicKind::Loop] are of the infinite curve extended from
+/// the bezier, so they may be out of [0, 1].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CubicKind {
+    /// All control points are the same.
+    Point,
+    /// All control points are collinear.
+    Line,
+    /// Curve is a degree elevated quadratic curve.
+    Quadratic,
+    /// Curve has no self-intersection or cusp, but may has inflection points.
+    Serpentine,
+    /// Curve has a cusp at `t`.
+    Cusp { t: f64 },
+    /// Curve crosses itself, at `t1` and `t2` (`t1 < t2`) it passes the same point.
+    Loop { t1: f64, t2: f64 },
+}
+
+impl<P: Point2D> Bezier<P> {
+    /// Classify the cubic curve as serpentine, loop, cusp, or degenerated quadratic, line and
+    /// point.
+    pub fn classify(&self) -> CubicKind {
+        let points = [&self.start, &self.ctrl1, &self.ctrl2, &self.end];
+
+        let scale = points
+            .iter()
+            .map(|p| p.distance(&self.start))
+            .fold(0.0, f64::max);
+
+        if scale == 0.0 {
+            return CubicKind::Point;
+        }
+
+        let eps = CLASSIFY_EPSILON * scale;
+
+        // collinear check, against direction from start to the farthest point
+        let far = points
+            .iter()
+            .max_by(|a, b| a.distance(&self.start).total_cmp(&b.distance(&self.start)))
+            .unwrap()
+            .minus(&self.start)
+            .scale(1.0 / scale);
+        if points
+            .iter()
+            .all(|p| libm::fabs(p.minus(&self.start).cross(&far)) <= eps)
+        {
+            return CubicKind::Line;
+        }
+
+        let [a, b, c, _] = self.parametric_function_coefficients();
+
+        if a.length_from_origin() <= eps {
+            return CubicKind::Quadratic;
+        }
+
+        // B(s) = B(t) for s != t, divide by (s - t):
+        //
+        //     a(s^2 + st + t^2) + b(s + t) + c = 0
+        //
+        // let u = s + t, v = st, then a(u^2 - v) + bu + c = 0. Cross with a eliminates v:
+        //
+        //     u(b x a) + (c x a) = 0
+        let ba = b.cross(&a);
+        let ca = c.cross(&a);
+
+        // double point at infinity, no loop or cusp
+        if libm::fabs(ba) <= eps * a.length_from_origin() * scale {
+            return CubicKind::Serpentine;
+        }
+
+        let u = -ca / ba;
+        let v = u * u + b.scale(u).plus(&c).dot(&a) / a.dot(&a);
+
+        // s, t are roots of x^2 - ux + v = 0
+        let disc = u * u - 4.0 * v;
+
+        if libm::fabs(disc) <= CLASSIFY_EPSILON * (u * u + 4.0 * libm::fabs(v)) {
+            CubicKind::Cusp { t: u / 2.0 }
+        } else if disc > 0.0 {
+            let r = libm::sqrt(disc);
+            CubicKind::Loop {
+                t1: (u - r) / 2.0,
+                t2: (u + r) / 2.0,
+            }
+        } else {
+            CubicKind::Serpentine
+        }
+    }
+
+    /// Parameters of self-intersection point, only if both are in [0, 1].
+    pub fn self_intersection(&self) -> Option<(f64, f64)> {
+        match self.classify() {
+            CubicKind::Loop { t1, t2 } if 0.0 <= t1 && t2 <= 1.0 => Some((t1, t2)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn classify_degenerated() {
+        let p = (1.0, 1.0);
+        assert_eq!(Bezier::new(p, p, p, p).classify(), CubicKind::Point);
+
+        let b = Bezier::new((0.0, 0.0), (3.0, 3.0), (1.0, 1.0), (2.0, 2.0));
+        assert_eq!(b.classify(), CubicKind::Line);
+
+        let b = Bezier::new_quad((0.0, 0.0), (1.0, 2.0), (2.0, 0.0));
+        assert_eq!(b.classify(), CubicKind::Quadratic);
+    }
+
+    #[test]
+    fn classify_loop() {
+        let b = Bezier::new((0.0, 0.0), (3.0, 3.0), (-1.0, 3.0), (2.0, 0.0));
+        let CubicKind::Loop { t1, t2 } = b.classify() else {
+            panic!("{:?}", b.classify());
+        };
+        assert!(b.at(t1).distance(&b.at(t2)) < 1e-9);
+        assert_eq!(b.self_intersection(), Some((t1, t2)));
+    }
+
+    #[test]
+    fn classify_cusp_and_serpentine() {
+        let b = Bezier::new((0.0, 0.0), (2.0, 2.0), (0.0, 2.0), (2.0, 0.0));
+        let CubicKind::Cusp { t } = b.classify() else {
+            panic!("{:?}", b.classify());
+        };
+        assert!((t - 0.5).abs() < 1e-9);
+        assert!(b.derivative(t).length_from_origin() < 1e-9);
+
+        let b = Bezier::new((0.0, 0.0), (1.0, 2.0), (2.0, -2.0), (3.0, 0.0));
+        assert_eq!(b.classify(), CubicKind::Serpentine);
+        assert_eq!(b.self_intersection(), None);
+    }
+}
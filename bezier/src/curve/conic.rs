@@ -0,0 +1,277 @@
+use alloc::vec::Vec;
+
+use super::{Nearest, QuadBezier};
+use crate::{Point2D, Poly, Rect, Root};
+
+/// Rational quadratic bezier curve in standard form, whose endpoints have weight 1 and control
+/// point has weight `weight`.
+///
+/// It represents conic sections exactly: weight less than 1 is an ellipse arc, 1 is a parabola
+/// (the same as [QuadBezier]), and greater than 1 is a hyperbola arc. A circular arc of angle
+/// θ (less than 180 degree) has weight cos(θ/2), with control point at the intersection of the
+/// tangents at its endpoints.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conic<P> {
+    pub start: P,
+    pub ctrl: P,
+    pub end: P,
+    pub weight: f64,
+}
+
+impl<P> Conic<P> {
+    pub fn new(start: P, ctrl: P, end: P, weight: f64) -> Self {
+        Self {
+            start,
+            ctrl,
+            end,
+            weight,
+        }
+    }
+}
+
+impl<P: Point2D> Conic<P> {
+    // Coefficients of numerator N(t) = a t^2 + b t + c, which is the weighted sum of points.
+    fn numerator_coefficients(&self) -> [P; 3] {
+        let wc = self.ctrl.scale(self.weight);
+
+        [
+            self.start.minus(&wc.scale(2.0)).plus(&self.end),
+            wc.minus(&self.start).scale(2.0),
+            self.start.clone(),
+        ]
+    }
+
+    // Denominator D(t), which is the sum of weights.
+    fn denominator(&self) -> Poly {
+        let w = self.weight;
+
+        [2.0 - 2.0 * w, 2.0 * w - 2.0, 1.0].into_iter().collect()
+    }
+
+    // Numerator polynomials of x and y.
+    fn numerator(&self) -> [Poly; 2] {
+        let [a, b, c] = self.numerator_coefficients();
+
+        [
+            [a.x(), b.x(), c.x()].into_iter().collect(),
+            [a.y(), b.y(), c.y()].into_iter().collect(),
+        ]
+    }
+
+    pub fn parametric_function(&self) -> impl Fn(f64) -> P {
+        let [a, b, c] = self.numerator_coefficients();
+        let w = self.weight;
+
+        move |t| {
+            let d = (2.0 - 2.0 * w) * t * t + (2.0 * w - 2.0) * t + 1.0;
+            a.scale(t * t).plus(&b.scale(t)).plus(&c).scale(1.0 / d)
+        }
+    }
+
+    pub fn at(&self, t: f64) -> P {
+        assert!((0.0..=1.0).contains(&t));
+        self.parametric_function()(t)
+    }
+
+    /// The quadratic curve if weight is 1, which is when the conic is a parabola.
+    pub fn as_quad(&self) -> Option<QuadBezier<P>> {
+        (self.weight == 1.0)
+            .then(|| QuadBezier::new(self.start.clone(), self.ctrl.clone(), self.end.clone()))
+    }
+
+    /// First derivative B'(t), which is (N'D - ND') / D^2.
+    pub fn derivative(&self, t: f64) -> P {
+        let [a, b, c] = self.numerator_coefficients();
+        let d = self.denominator();
+
+        let n = a.scale(t * t).plus(&b.scale(t)).plus(&c);
+        let dn = a.scale(2.0 * t).plus(&b);
+        let dv = d.eval(t);
+
+        dn.scale(dv)
+            .minus(&n.scale(d.derivative().eval(t)))
+            .scale(1.0 / (dv * dv))
+    }
+
+    /// Unit tangent vector at t.
+    ///
+    /// Where the first derivative is zero, which is when control point coincides with endpoint,
+    /// the direction from endpoint to the other one is used.
+    pub fn tangent(&self, t: f64) -> P {
+        let d = self.derivative(t);
+
+        if d.length_from_origin() != 0.0 {
+            d.normalize()
+        } else {
+            self.end.minus(&self.start).normalize()
+        }
+    }
+
+    /// Unit normal vector at t, which is the tangent rotated 90 degree counterclockwise.
+    pub fn normal(&self, t: f64) -> P {
+        let tangent = self.tangent(t);
+
+        P::from_xy(-tangent.y(), tangent.x())
+    }
+
+    /// Split the curve at t, both parts are still in standard form.
+    ///
+    /// Parts are reparameterized to the standard form, so their parameters do not map to the
+    /// original one linearly.
+    pub fn split_at(&self, t: f64) -> (Self, Self) {
+        // de casteljau in homogeneous coordinates, (point * weight, weight)
+        let lerp = |(p1, w1): (&P, f64), (p2, w2): (&P, f64)| {
+            let w = w1 + (w2 - w1) * t;
+            let p = p1
+                .scale(w1 * (1.0 - t))
+                .plus(&p2.scale(w2 * t))
+                .scale(1.0 / w);
+            (p, w)
+        };
+
+        let (p01, w01) = lerp((&self.start, 1.0), (&self.ctrl, self.weight));
+        let (p12, w12) = lerp((&self.ctrl, self.weight), (&self.end, 1.0));
+        let (p, w) = lerp((&p01, w01), (&p12, w12));
+
+        // weights of (1, w01, w) and (w, w12, 1) in standard form
+        let left = Self::new(self.start.clone(), p01, p.clone(), w01 / libm::sqrt(w));
+        let right = Self::new(p, p12, self.end.clone(), w12 / libm::sqrt(w));
+
+        (left, right)
+    }
+
+    // Numerator of d/dt |B(t) - target|^2 / 2 after clearing denominators.
+    //
+    // With M = N - target * D, B - target = M / D and B' = (M'D - MD') / D^2, so the derivative
+    // is zero where M . (M'D - MD') is zero, which is a quartic polynomial.
+    fn distance_derivative(&self, target: &P) -> Poly {
+        let d = self.denominator();
+        let dd = d.derivative();
+        let [nx, ny] = self.numerator();
+
+        let component = |n: Poly, v: f64| {
+            let m = n.sub(&d.mul(&[v].into_iter().collect()));
+            let dm = m.derivative().mul(&d).sub(&m.mul(&dd));
+            m.mul(&dm)
+        };
+
+        component(nx, target.x()).add(&component(ny, target.y()))
+    }
+
+    pub fn nearest_to(&self, target: &P, allow_endpoint: bool) -> Option<Nearest<P>> {
+        // We only need real root between (0, 1) because we add endpoints according to param
+        let roots = match self.distance_derivative(target).real_roots_in(0.0..=1.0) {
+            Root::Roots(roots) => roots,
+            Root::None | Root::Any => Vec::new(),
+        };
+
+        let endpoints = if allow_endpoint {
+            Some(0.0).into_iter().chain(Some(1.0))
+        } else {
+            None.into_iter().chain(None)
+        };
+
+        roots
+            .into_iter()
+            .filter(|t| 0.0 < *t && *t < 1.0)
+            .chain(endpoints)
+            .map(|t| Nearest::new_from_conic(self, t, target))
+            .min()
+    }
+
+    /// Sorted t values in [0, 1] where x'(t) or y'(t) is zero.
+    pub fn extrema(&self) -> Vec<f64> {
+        let d = self.denominator();
+        let dd = d.derivative();
+
+        let mut roots: Vec<_> = self
+            .numerator()
+            .into_iter()
+            .map(|n| n.derivative().mul(&d).sub(&n.mul(&dd)))
+            .flat_map(|poly| match poly.real_roots_in(0.0..=1.0) {
+                Root::Roots(roots) => roots,
+                Root::None | Root::Any => Vec::new(),
+            })
+            .collect();
+        roots.sort_by(f64::total_cmp);
+        roots.dedup();
+
+        roots
+    }
+
+    /// Bounding box of the three control points, which contains the curve when weight is
+    /// positive.
+    pub fn control_bounds(&self) -> Rect<P> {
+        Rect::from_points([&self.start, &self.ctrl, &self.end]).unwrap()
+    }
+
+    /// Exact bounding box of the curve.
+    pub fn bounds(&self) -> Rect<P> {
+        let mut rect = Rect::new(self.start.clone(), self.end.clone());
+
+        let f = self.parametric_function();
+        for t in self.extrema() {
+            rect.include(&f(t));
+        }
+
+        rect
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::f64::consts::FRAC_1_SQRT_2;
+
+    use super::*;
+
+    // quarter of the unit circle, from (1, 0) to (0, 1)
+    fn quarter() -> Conic<(f64, f64)> {
+        Conic::new((1.0, 0.0), (1.0, 1.0), (0.0, 1.0), FRAC_1_SQRT_2)
+    }
+
+    #[test]
+    fn conic_circle_exact() {
+        let c = quarter();
+
+        for i in 0..=16 {
+            let t = i as f64 / 16.0;
+            assert!((c.at(t).length_from_origin() - 1.0).abs() < 1e-15);
+            assert!(c.derivative(t).dot(&c.at(t)).abs() < 1e-12);
+        }
+        assert!(c.at(0.5).distance(&(FRAC_1_SQRT_2, FRAC_1_SQRT_2)) < 1e-15);
+
+        let (left, right) = c.split_at(0.3);
+        assert!(left.end.distance(&c.at(0.3)) < 1e-15);
+        assert!(right.start.distance(&c.at(0.3)) < 1e-15);
+        // still circular arcs
+        for i in 0..=16 {
+            let t = i as f64 / 16.0;
+            assert!((left.at(t).length_from_origin() - 1.0).abs() < 1e-15);
+            assert!((right.at(t).length_from_origin() - 1.0).abs() < 1e-15);
+        }
+    }
+
+    #[test]
+    fn conic_nearest() {
+        let c = quarter();
+        let target = (2.0, 3.0);
+
+        let nearest = c.nearest_to(&target, true).unwrap();
+        let expect = (2.0, 3.0).normalize();
+        assert!(nearest.point.distance(&expect) < 1e-9);
+        assert!((nearest.distance - (libm::sqrt(13.0) - 1.0)).abs() < 1e-9);
+
+        let hyperbola = Conic::new((0.0, 0.0), (5.0, 10.0), (10.0, 0.0), 3.0);
+        let target = (6.0, 4.0);
+        let nearest = hyperbola.nearest_to(&target, true).unwrap();
+        let brute = (0..=10000)
+            .map(|i| hyperbola.at(i as f64 / 10000.0).distance(&target))
+            .fold(f64::INFINITY, f64::min);
+        assert!(nearest.distance <= brute + 1e-12);
+        assert!(nearest.distance > brute - 1e-3);
+
+        let bounds = c.bounds();
+        assert!((bounds.width() - 1.0).abs() < 1e-12);
+        assert!((bounds.height() - 1.0).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,198 @@
+use alloc::vec::Vec;
+
+use super::{Arc, Bezier, Curve, Segment};
+use crate::Point2D;
+
+// Max subdivision depth, limits the smallest piece to 2^-24 of the curve.
+const MAX_DEPTH: usize = 24;
+
+// Min tolerance relative to size of the control point bounds. Smaller ones, including zero and
+// negative, are raised to it, or a tiny tolerance would split the curve into millions of points.
+const MIN_RELATIVE_TOLERANCE: f64 = 1e-9;
+
+// Distance from point to the segment.
+fn distance_to_segment<P: Point2D>(p: &P, start: &P, end: &P) -> f64 {
+    let d = end.minus(start);
+    let len2 = d.dot(&d);
+
+    if len2 == 0.0 {
+        return p.distance(start);
+    }
+
+    let t = (p.minus(start).dot(&d) / len2).clamp(0.0, 1.0);
+    p.distance(&start.plus(&d.scale(t)))
+}
+
+impl<P: Point2D> Segment<P> {
+    pub(crate) fn flatten_into(&self, points: &mut Vec<P>) {
+        points.push(self.end().clone());
+    }
+
+    /// Polyline of the segment, which is its two endpoints.
+    pub fn flatten(&self) -> Vec<P> {
+        vec![self.start().clone(), self.end().clone()]
+    }
+}
+
+impl<P: Point2D> Bezier<P> {
+    // The curve lies in the convex hull of its control points, and distance to the chord is a
+    // convex function, so the max distance from control points to chord bounds the deviation.
+    fn flatness(&self) -> f64 {
+        f64::max(
+            distance_to_segment(&self.ctrl1, &self.start, &self.end),
+            distance_to_segment(&self.ctrl2, &self.start, &self.end),
+        )
+    }
+
+    fn flatten_recursive(&self, tolerance: f64, depth: usize, points: &mut Vec<P>) {
+        if depth == 0 || self.flatness() <= tolerance {
+            points.push(self.end.clone());
+            return;
+        }
+
+        let (left, right) = self.split_at(0.5);
+        left.flatten_recursive(tolerance, depth - 1, points);
+        right.flatten_recursive(tolerance, depth - 1, points);
+    }
+
+    // Push points of the polyline into `points`, except the start point.
+    pub(crate) fn flatten_into(&self, tolerance: f64, points: &mut Vec<P>) {
+        let bounds = self.control_bounds();
+        let size = f64::max(bounds.width(), bounds.height());
+        let tolerance = f64::max(tolerance, size * MIN_RELATIVE_TOLERANCE);
+
+        self.flatten_recursive(tolerance, MAX_DEPTH, points);
+    }
+
+    /// Polyline approximates the curve, any point on the curve is within `tolerance` to it.
+    ///
+    /// Curve is subdivided adaptively, so flat parts get fewer points than sharp turns.
+    /// `tolerance` is at least 1e-9 of the control point bounds size, smaller or non-positive
+    /// ones are raised to it.
+    pub fn flatten(&self, tolerance: f64) -> Vec<P> {
+        let mut points = vec![self.start.clone()];
+        self.flatten_into(tolerance, &mut points);
+        points
+    }
+}
+
+impl<P: Point2D> Arc<P> {
+    // Push points of the polyline into `points`, except the start point.
+    //
+    // Distance from a circular arc of `step` radians to its chord is r(1 - cos(step/2)). An
+    // elliptical arc is the circular one mapped linearly, so the larger radius bounds it.
+    pub(crate) fn flatten_into(&self, tolerance: f64, points: &mut Vec<P>) {
+        let r = self.max_radius();
+        let tolerance = f64::max(tolerance, 2.0 * r * MIN_RELATIVE_TOLERANCE);
+
+        let step = 2.0 * libm::acos((1.0 - tolerance / r).clamp(-1.0, 1.0));
+        let n = libm::ceil(libm::fabs(self.sweep_angle.to_radians()) / step).max(1.0) as usize;
+
+        points.extend((1..=n).map(|i| self.at(i as f64 / n as f64)));
+    }
+
+    /// Polyline approximates the arc, any point on the arc is within `tolerance` to it.
+    ///
+    /// Points are evenly spaced in angle. `tolerance` is at least 1e-9 of the larger diameter,
+    /// smaller or non-positive ones are raised to it.
+    pub fn flatten(&self, tolerance: f64) -> Vec<P> {
+        let mut points = vec![self.start()];
+        self.flatten_into(tolerance, &mut points);
+        points
+    }
+}
+
+impl<P: Point2D> Curve<P> {
+    pub(crate) fn flatten_into(&self, tolerance: f64, points: &mut Vec<P>) {
+        match self {
+            Self::Quad(q) => q.to_cubic().flatten_into(tolerance, points),
+            Self::Bezier(b) => b.flatten_into(tolerance, points),
+            Self::Arc(a) => a.flatten_into(tolerance, points),
+            Self::Segment(l) => l.flatten_into(points),
+        }
+    }
+
+    /// Polyline approximates the curve, with max deviation `tolerance`.
+    ///
+    /// Result starts with the start point and ends with the end point of the curve. See
+    /// [Bezier::flatten] and [Arc::flatten] for the min tolerance.
+    pub fn flatten(&self, tolerance: f64) -> Vec<P> {
+        match self {
+            Self::Quad(q) => q.to_cubic().flatten(tolerance),
+            Self::Bezier(b) => b.flatten(tolerance),
+            Self::Arc(a) => a.flatten(tolerance),
+            Self::Segment(l) => l.flatten(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn flatten_within_tolerance() {
+        let b = Bezier::new((0.0, 0.0), (0.0, 100.0), (300.0, 100.0), (300.0, 0.0));
+
+        for tolerance in [1.0, 0.1, 0.01] {
+            let points = b.flatten(tolerance);
+            assert_eq!(points.first(), Some(&b.start));
+            assert_eq!(points.last(), Some(&b.end));
+
+            for i in 0..=200 {
+                let p = b.at(i as f64 / 200.0);
+                let d = points
+                    .windows(2)
+                    .map(|w| distance_to_segment(&p, &w[0], &w[1]))
+                    .fold(f64::INFINITY, f64::min);
+                assert!(d <= tolerance, "{d} > {tolerance}");
+            }
+        }
+    }
+
+    #[test]
+    fn flatten_min_tolerance() {
+        let b = Bezier::new((0.0, 0.0), (0.0, 100.0), (300.0, 100.0), (300.0, 0.0));
+        let min = b.flatten(300.0 * MIN_RELATIVE_TOLERANCE);
+
+        for tolerance in [0.0, -1.0, f64::NAN] {
+            assert_eq!(b.flatten(tolerance), min);
+        }
+        assert!(min.len() < 1 << 16);
+    }
+
+    #[test]
+    fn flatten_arc_within_tolerance() {
+        // M100 0 A100 100 0 0 1 0 100, and a rotated ellipse
+        let arcs = [
+            Arc::new((0.0, 0.0), (100.0, 100.0), 0.0, 0.0, 90.0),
+            Arc::new((1.0, 2.0), (200.0, 100.0), 30.0, 200.0, -300.0),
+        ];
+
+        for arc in arcs {
+            for tolerance in [1.0, 1e-2, 1e-4, 1e-6] {
+                let points = arc.flatten(tolerance);
+                assert_eq!(points.first(), Some(&arc.start()));
+                assert_eq!(points.last(), Some(&arc.end()));
+
+                // points are evenly spaced, so only the segment around the sample is checked
+                let n = points.len() - 1;
+                for i in 0..=1000 {
+                    let t = i as f64 / 1000.0;
+                    let k = ((t * n as f64) as usize).min(n - 1);
+                    let d = distance_to_segment(&arc.at(t), &points[k], &points[k + 1]);
+                    assert!(d <= tolerance, "{d} > {tolerance}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn flatten_adaptive() {
+        let small = Bezier::new((0.0, 0.0), (0.0, 1.0), (3.0, 1.0), (3.0, 0.0));
+        let large = Bezier::new((0.0, 0.0), (0.0, 100.0), (300.0, 100.0), (300.0, 0.0));
+
+        assert!(small.flatten(0.1).len() < large.flatten(0.1).len());
+        assert_eq!(Segment::new((0.0, 0.0), (1.0, 1.0)).flatten().len(), 2);
+    }
+}
@@ -0,0 +1,759 @@
+use alloc::vec::Vec;
+
+use super::{Arc, Bezier, Curve, Segment};
+use crate::{Point2D, Poly, Rect, Root};
+
+// Max sine of the angle between two tangent directions which still be treated as parallel.
+const TANGENT_TOLERANCE: f64 = 1e-6;
+
+// Max recursion depth of bezier subdivision.
+const MAX_DEPTH: usize = 48;
+
+// Max count of curve pairs visited by bezier subdivision, prevent explosion on nearly overlapped
+// curves. Pairs still near when reaching it are kept as rough candidates instead of subdivided.
+const MAX_VISITS: usize = 1 << 16;
+
+// Max iteration count of Newton's method when refining an intersection.
+const MAX_ITERATION: usize = 16;
+
+// Sample count when checking two bezier curves overlap.
+const OVERLAP_SAMPLES: usize = 8;
+
+// Max distance between an arc and the cubic curves to find its rough intersections, relative to
+// its larger radius.
+const ARC_APPROX_TOLERANCE: f64 = 1e-6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntersectionKind {
+    /// Curves cross each other at the point.
+    Cross,
+    /// Curves touch each other at the point, their tangent directions are parallel.
+    Tangent,
+    /// Start or end of an interval where two curves coincide.
+    Overlap,
+}
+
+/// An intersection point of two curves.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Intersection<P> {
+    /// Parameter of intersection point on curve which the query is called on.
+    pub t_self: f64,
+    /// Parameter of intersection point on the other curve.
+    pub t_other: f64,
+    pub point: P,
+    pub kind: IntersectionKind,
+}
+
+impl<P> Intersection<P> {
+    fn new(t_self: f64, t_other: f64, point: P, kind: IntersectionKind) -> Self {
+        Self {
+            t_self,
+            t_other,
+            point,
+            kind,
+        }
+    }
+
+    fn swap(self) -> Self {
+        Self {
+            t_self: self.t_other,
+            t_other: self.t_self,
+            ..self
+        }
+    }
+}
+
+fn is_parallel<P: Point2D>(d1: &P, d2: &P) -> bool {
+    let l = d1.length_from_origin() * d2.length_from_origin();
+    l != 0.0 && libm::fabs(d1.cross(d2)) <= TANGENT_TOLERANCE * l
+}
+
+fn kind_by_direction<P: Point2D>(d1: &P, d2: &P) -> IntersectionKind {
+    if is_parallel(d1, d2) {
+        IntersectionKind::Tangent
+    } else {
+        IntersectionKind::Cross
+    }
+}
+
+fn boxes_near<P: Point2D>(a: &Rect<P>, b: &Rect<P>, tolerance: f64) -> bool {
+    a.min().x() - tolerance <= b.max().x()
+        && b.min().x() - tolerance <= a.max().x()
+        && a.min().y() - tolerance <= b.max().y()
+        && b.min().y() - tolerance <= a.max().y()
+}
+
+fn roots_in_unit(poly: &Poly) -> Vec<f64> {
+    match poly.real_roots_in(0.0..=1.0) {
+        Root::Roots(roots) => roots,
+        Root::None | Root::Any => Vec::new(),
+    }
+}
+
+// Project point onto segment, returns parameter t, not clamped.
+fn project<P: Point2D>(segment: &Segment<P>, p: &P) -> f64 {
+    let d = segment.derivative();
+    p.minus(segment.start()).dot(&d) / d.dot(&d)
+}
+
+fn segment_segment<P: Point2D>(
+    a: &Segment<P>, b: &Segment<P>, tolerance: f64,
+) -> Vec<Intersection<P>> {
+    let d1 = a.derivative();
+    let d2 = b.derivative();
+    let l1 = d1.length_from_origin();
+    let l2 = d2.length_from_origin();
+
+    // degenerated to point
+    if l1 == 0.0 || l2 == 0.0 {
+        let (p, seg, swap) = if l1 == 0.0 {
+            (a.start(), b, false)
+        } else {
+            (b.start(), a, true)
+        };
+        return match seg.nearest_to(p, true) {
+            Some(n) if n.distance <= tolerance => {
+                let hit = Intersection::new(0.0, n.t, n.point, IntersectionKind::Cross);
+                vec![if swap { hit.swap() } else { hit }]
+            }
+            _ => Vec::new(),
+        };
+    }
+
+    let w = b.start().minus(a.start());
+    let denom = d1.cross(&d2);
+
+    if !is_parallel(&d1, &d2) {
+        let s = w.cross(&d2) / denom;
+        let t = w.cross(&d1) / denom;
+
+        let (es, et) = (tolerance / l1, tolerance / l2);
+        if (-es..=1.0 + es).contains(&s) && (-et..=1.0 + et).contains(&t) {
+            let s = s.clamp(0.0, 1.0);
+            let t = t.clamp(0.0, 1.0);
+            return vec![Intersection::new(s, t, a.at(s), IntersectionKind::Cross)];
+        }
+
+        return Vec::new();
+    }
+
+    // parallel but not on same line
+    if libm::fabs(w.cross(&d1)) / l1 > tolerance {
+        return Vec::new();
+    }
+
+    // collinear, find overlapped interval on a
+    let s0 = project(a, b.start());
+    let s1 = project(a, b.end());
+    let lo = f64::max(0.0, f64::min(s0, s1));
+    let hi = f64::min(1.0, f64::max(s0, s1));
+
+    if (hi - lo) * l1 < -tolerance {
+        return Vec::new();
+    }
+
+    let hit = |s: f64, kind| {
+        let p = a.at(s.clamp(0.0, 1.0));
+        let t = project(b, &p).clamp(0.0, 1.0);
+        Intersection::new(s.clamp(0.0, 1.0), t, p, kind)
+    };
+
+    if (hi - lo) * l1 <= tolerance {
+        vec![hit((lo + hi) / 2.0, IntersectionKind::Tangent)]
+    } else {
+        vec![
+            hit(lo, IntersectionKind::Overlap),
+            hit(hi, IntersectionKind::Overlap),
+        ]
+    }
+}
+
+fn bezier_segment<P: Point2D>(
+    a: &Bezier<P>, b: &Segment<P>, tolerance: f64,
+) -> Vec<Intersection<P>> {
+    let d = b.derivative();
+    let l = d.length_from_origin();
+
+    // degenerated to point
+    if l == 0.0 {
+        return match a.nearest_to(b.start(), true) {
+            Some(n) if n.distance <= tolerance => {
+                vec![Intersection::new(
+                    n.t,
+                    0.0,
+                    n.point,
+                    IntersectionKind::Cross,
+                )]
+            }
+            _ => Vec::new(),
+        };
+    }
+
+    // signed distance to the line and projected parameter on segment, both are cubic of t
+    let normal = P::from_xy(-d.y(), d.x()).scale(1.0 / l);
+    let [c3, c2, c1, c0] = a.parametric_function_coefficients();
+    let c0 = c0.minus(b.start());
+    let l2 = l * l;
+    let distance: Poly = [
+        c3.dot(&normal),
+        c2.dot(&normal),
+        c1.dot(&normal),
+        c0.dot(&normal),
+    ]
+    .into_iter()
+    .collect();
+    let param = |offset: f64| -> Poly {
+        [
+            c3.dot(&d) / l2,
+            c2.dot(&d) / l2,
+            c1.dot(&d) / l2,
+            c0.dot(&d) / l2 - offset,
+        ]
+        .into_iter()
+        .collect()
+    };
+    let s = param(0.0);
+
+    let e = tolerance / l;
+    let on_segment = |t: f64| (-e..=1.0 + e).contains(&s.eval(t));
+
+    let hit = |t: f64, kind| {
+        let p = a.at(t);
+        Intersection::new(t, s.eval(t).clamp(0.0, 1.0), p, kind)
+    };
+
+    // bezier lies on the line
+    if distance.is_zero() {
+        let mut bounds: Vec<f64> = [0.0, 1.0]
+            .into_iter()
+            .chain(roots_in_unit(&s))
+            .chain(roots_in_unit(&param(1.0)))
+            .collect();
+        bounds.sort_by(f64::total_cmp);
+        bounds.dedup();
+
+        // merge adjacent overlapped intervals
+        let mut runs: Vec<(f64, f64)> = Vec::new();
+        for w in bounds.windows(2) {
+            if !on_segment((w[0] + w[1]) / 2.0) {
+                continue;
+            }
+            match runs.last_mut() {
+                Some(last) if last.1 == w[0] => last.1 = w[1],
+                _ => runs.push((w[0], w[1])),
+            }
+        }
+
+        return runs
+            .into_iter()
+            .flat_map(|(t0, t1)| {
+                if a.at(t0).distance(&a.at(t1)) <= tolerance {
+                    vec![hit((t0 + t1) / 2.0, IntersectionKind::Tangent)]
+                } else {
+                    vec![
+                        hit(t0, IntersectionKind::Overlap),
+                        hit(t1, IntersectionKind::Overlap),
+                    ]
+                }
+            })
+            .collect();
+    }
+
+    // Touch points may not be found as roots due to floating error, so local extremum of distance
+    // and endpoints near the line are also candidates, flagged as true.
+    let extrema = roots_in_unit(&distance.derivative())
+        .into_iter()
+        .chain([0.0, 1.0])
+        .filter(|t| libm::fabs(distance.eval(*t)) <= tolerance)
+        .map(|t| (t, true));
+    let mut candidates: Vec<(f64, bool)> = roots_in_unit(&distance)
+        .into_iter()
+        .map(|t| (t, false))
+        .chain(extrema)
+        .filter(|(t, _)| on_segment(*t))
+        .collect();
+    candidates.sort_by(|x, y| x.0.total_cmp(&y.0));
+
+    // candidates between which curve keeps near the line are the same intersection, prefer the
+    // extremum one because it's more accurate for touch point
+    let mut clusters: Vec<(f64, bool)> = Vec::new();
+    for (t, flag) in candidates {
+        match clusters.last_mut() {
+            Some(last) if libm::fabs(distance.eval((last.0 + t) / 2.0)) <= tolerance => {
+                if flag && !last.1 {
+                    *last = (t, flag);
+                }
+            }
+            _ => clusters.push((t, flag)),
+        }
+    }
+
+    clusters
+        .into_iter()
+        .map(|(t, _)| hit(t, kind_by_direction(&a.derivative(t), &d)))
+        .collect()
+}
+
+// Find parameter pairs where endpoints of one bezier lie on another, and check if the curves
+// coincide between them.
+fn bezier_overlap<P: Point2D>(
+    a: &Bezier<P>, b: &Bezier<P>, tolerance: f64,
+) -> Option<Vec<Intersection<P>>> {
+    let mut pairs: Vec<(f64, f64)> = Vec::new();
+
+    for (s, p) in [(0.0, &a.start), (1.0, &a.end)] {
+        if let Some(n) = b.nearest_to(p, true).filter(|n| n.distance <= tolerance) {
+            pairs.push((s, n.t));
+        }
+    }
+    for (t, p) in [(0.0, &b.start), (1.0, &b.end)] {
+        if let Some(n) = a.nearest_to(p, true).filter(|n| n.distance <= tolerance) {
+            pairs.push((n.t, t));
+        }
+    }
+
+    pairs.sort_by(|x, y| x.0.total_cmp(&y.0));
+    pairs.dedup_by(|x, y| a.at(x.0).distance(&a.at(y.0)) <= tolerance);
+
+    let mut result: Vec<Intersection<P>> = Vec::new();
+
+    for w in pairs.windows(2) {
+        let [(s0, t0), (s1, t1)] = [w[0], w[1]];
+
+        let coincide = (1..OVERLAP_SAMPLES).all(|i| {
+            let s = s0 + (s1 - s0) * i as f64 / OVERLAP_SAMPLES as f64;
+            b.nearest_to(&a.at(s), true)
+                .is_some_and(|n| n.distance <= tolerance)
+        });
+
+        if coincide {
+            if result.last().is_none_or(|last| last.t_self != s0) {
+                result.push(Intersection::new(
+                    s0,
+                    t0,
+                    a.at(s0),
+                    IntersectionKind::Overlap,
+                ));
+            }
+            result.push(Intersection::new(
+                s1,
+                t1,
+                a.at(s1),
+                IntersectionKind::Overlap,
+            ));
+        }
+    }
+
+    if result.is_empty() {
+        None
+    } else {
+        Some(result)
+    }
+}
+
+// Candidate parameter pairs found by bezier subdivision.
+#[derive(Default)]
+struct Candidates {
+    // count of curve pairs visited
+    visits: usize,
+    pairs: Vec<(f64, f64)>,
+}
+
+fn subdivide<P: Point2D>(
+    a: &Bezier<P>, ar: (f64, f64), b: &Bezier<P>, br: (f64, f64), tolerance: f64, depth: usize,
+    out: &mut Candidates,
+) {
+    out.visits += 1;
+
+    let ba = a.control_bounds();
+    let bb = b.control_bounds();
+
+    if !boxes_near(&ba, &bb, tolerance) {
+        return;
+    }
+
+    let size = |r: &Rect<P>| f64::max(r.width(), r.height());
+    if out.visits >= MAX_VISITS
+        || depth >= MAX_DEPTH
+        || (size(&ba) <= tolerance && size(&bb) <= tolerance)
+    {
+        out.pairs.push(((ar.0 + ar.1) / 2.0, (br.0 + br.1) / 2.0));
+        return;
+    }
+
+    let (a1, a2) = a.split_at(0.5);
+    let (b1, b2) = b.split_at(0.5);
+    let am = (ar.0 + ar.1) / 2.0;
+    let bm = (br.0 + br.1) / 2.0;
+
+    for (a, ar) in [(&a1, (ar.0, am)), (&a2, (am, ar.1))] {
+        for (b, br) in [(&b1, (br.0, bm)), (&b2, (bm, br.1))] {
+            subdivide(a, ar, b, br, tolerance, depth + 1, out);
+        }
+    }
+}
+
+// Point, first and second derivative at t, which is what newton refinement needs.
+fn evaluator<P: Point2D>(c: &Bezier<P>) -> impl Fn(f64) -> (P, P, P) + '_ {
+    |t| (c.at(t), c.derivative(t), c.second_derivative(t))
+}
+
+// Refine an intersection candidate, returns parameters and distance of the nearest pair found.
+//
+// Crossing is solved by Newton's method on A(s) - B(t) = 0. Its jacobian is singular where
+// curves touch, and the gap there only grows quadratically, so when curves are parallel or not
+// close enough the touch point is solved on (A(s) - B(t)) . B'(t) = 0, foot point of A(s) on B,
+// and A'(s) x B'(t) = 0, parallel tangent directions.
+fn refine<P: Point2D>(
+    a: impl Fn(f64) -> (P, P, P), b: impl Fn(f64) -> (P, P, P), s: f64, t: f64, tolerance: f64,
+) -> (f64, f64, f64) {
+    let gap = |s, t| a(s).0.distance(&b(t).0);
+
+    let (mut s, mut t) = (s, t);
+    let mut best = (s, t, gap(s, t));
+
+    for _ in 0..MAX_ITERATION {
+        let (pa, da, _) = a(s);
+        let (pb, db, _) = b(t);
+        let f = pa.minus(&pb);
+
+        let det = -da.cross(&db);
+        if det == 0.0 {
+            break;
+        }
+
+        s = (s + f.cross(&db) / det).clamp(0.0, 1.0);
+        t = (t - da.cross(&f) / det).clamp(0.0, 1.0);
+
+        let g = gap(s, t);
+        if g < best.2 {
+            best = (s, t, g);
+        }
+        if g == 0.0 {
+            break;
+        }
+    }
+
+    if best.2 <= tolerance && !is_parallel(&a(best.0).1, &b(best.1).1) {
+        return best;
+    }
+
+    let cross = best;
+    let (mut s, mut t) = (best.0, best.1);
+    for _ in 0..MAX_ITERATION {
+        let (pa, da, dda) = a(s);
+        let (pb, db, ddb) = b(t);
+        let f = pa.minus(&pb);
+
+        let f1 = f.dot(&db);
+        let f2 = da.cross(&db);
+        let [j11, j12] = [da.dot(&db), f.dot(&ddb) - db.dot(&db)];
+        let [j21, j22] = [dda.cross(&db), da.cross(&ddb)];
+
+        let det = j11 * j22 - j12 * j21;
+        if det == 0.0 {
+            break;
+        }
+
+        let (ds, dt) = ((f1 * j22 - f2 * j12) / det, (f2 * j11 - f1 * j21) / det);
+        s = (s - ds).clamp(0.0, 1.0);
+        t = (t - dt).clamp(0.0, 1.0);
+
+        if libm::fabs(ds) <= f64::EPSILON && libm::fabs(dt) <= f64::EPSILON {
+            break;
+        }
+    }
+
+    // touch point is more accurate than the crossing one if they are both close enough
+    let g = gap(s, t);
+    if g <= f64::max(tolerance, cross.2) {
+        (s, t, g)
+    } else {
+        cross
+    }
+}
+
+fn bezier_bezier<P: Point2D>(a: &Bezier<P>, b: &Bezier<P>, tolerance: f64) -> Vec<Intersection<P>> {
+    if let Some(overlap) = bezier_overlap(a, b, tolerance) {
+        return overlap;
+    }
+
+    let mut candidates = Candidates::default();
+    subdivide(a, (0.0, 1.0), b, (0.0, 1.0), tolerance, 0, &mut candidates);
+    candidates.pairs.sort_by(|x, y| x.0.total_cmp(&y.0));
+
+    let gap = |(s, t): (f64, f64)| a.at(s).distance(&b.at(t));
+    // candidates between which curves keep near are the same intersection
+    let near =
+        |x: (f64, f64), y: (f64, f64)| gap(((x.0 + y.0) / 2.0, (x.1 + y.1) / 2.0)) <= tolerance;
+
+    // group candidates of same intersection, keep the nearest and last one
+    type Params = (f64, f64);
+    let group = |candidates: Vec<Params>| {
+        let mut groups: Vec<(Params, f64, Params)> = Vec::new();
+        for c in candidates {
+            let g = gap(c);
+            match groups.last_mut() {
+                Some((best, best_gap, last)) if near(*last, c) => {
+                    if g < *best_gap {
+                        (*best, *best_gap) = (c, g);
+                    }
+                    *last = c;
+                }
+                _ => groups.push((c, g, c)),
+            }
+        }
+        groups.into_iter().map(|(best, ..)| best)
+    };
+
+    // refinement finds the nearest pair around a candidate, so a larger gap means the curves only
+    // pass by each other there
+    let mut refined: Vec<_> = group(candidates.pairs)
+        .map(|(s, t)| refine(evaluator(a), evaluator(b), s, t, tolerance))
+        .filter(|(_, _, gap)| *gap <= tolerance)
+        .map(|(s, t, _)| (s, t))
+        .collect();
+    refined.sort_by(|x, y| x.0.total_cmp(&y.0));
+
+    // different groups may be refined to the same intersection
+    group(refined)
+        .map(|(s, t)| {
+            let point = a.at(s).plus(&b.at(t)).scale(0.5);
+            let kind = kind_by_direction(&a.derivative(s), &b.derivative(t));
+            Intersection::new(s, t, point, kind)
+        })
+        .collect()
+}
+
+// Intersections of an arc and another curve.
+//
+// Arc is converted to cubic curves to find the rough intersections, with the approximation error
+// added to the tolerance so none is missed. Then each one is refined on the exact arc, and dropped
+// if the curves only pass by each other there.
+fn arc_curve<P: Point2D>(a: &Arc<P>, other: &Curve<P>, tolerance: f64) -> Vec<Intersection<P>> {
+    let approx = f64::max(tolerance, a.max_radius() * ARC_APPROX_TOLERANCE);
+    let cubics = a.to_cubics(approx);
+    let n = cubics.len() as f64;
+
+    let mut result: Vec<Intersection<P>> = Vec::new();
+    for (i, b) in cubics.into_iter().enumerate() {
+        for x in Curve::Bezier(b).intersections(other, tolerance + approx) {
+            // pieces split the sweep angle evenly, and parameter of each piece is close to angle
+            let s = (i as f64 + x.t_self) / n;
+            let (s, t, gap) = refine(
+                |s| (a.at(s), a.derivative(s), a.second_derivative(s)),
+                |t| (other.at(t), other.derivative(t), other.second_derivative(t)),
+                s,
+                x.t_other,
+                tolerance,
+            );
+            if gap > tolerance {
+                continue;
+            }
+
+            let point = a.at(s).plus(&other.at(t)).scale(0.5);
+
+            // same intersection found at the joint of two pieces
+            if result
+                .last()
+                .is_some_and(|last| last.point.distance(&point) <= tolerance)
+            {
+                continue;
+            }
+
+            // overlap can't be told from a single point, it's kept from the approximation
+            let kind = match x.kind {
+                IntersectionKind::Overlap => IntersectionKind::Overlap,
+                _ => kind_by_direction(&a.derivative(s), &other.derivative(t)),
+            };
+            result.push(Intersection::new(s, t, point, kind));
+        }
+    }
+
+    result
+}
+
+impl<P: Point2D> Curve<P> {
+    /// Find all intersection points of two curves, sorted by `t_self`.
+    ///
+    /// `tolerance` is the max distance between two curves which is treated as intersected.
+    ///
+    /// Touch points are reported as [IntersectionKind::Tangent]. If two curves coincide in an
+    /// interval, start and end of the interval are reported as [IntersectionKind::Overlap].
+    pub fn intersections(&self, other: &Self, tolerance: f64) -> Vec<Intersection<P>> {
+        // elevation keeps the parameter, so quadratic curves are solved as cubic ones
+        if let Self::Quad(q) = self {
+            return Self::Bezier(q.to_cubic()).intersections(other, tolerance);
+        }
+        if let Self::Quad(q) = other {
+            return self.intersections(&Self::Bezier(q.to_cubic()), tolerance);
+        }
+
+        let mut result = match (self, other) {
+            (Self::Arc(a), _) => arc_curve(a, other, tolerance),
+            (_, Self::Arc(b)) => arc_curve(b, self, tolerance)
+                .into_iter()
+                .map(Intersection::swap)
+                .collect(),
+            (Self::Segment(a), Self::Segment(b)) => segment_segment(a, b, tolerance),
+            (Self::Bezier(a), Self::Segment(b)) => bezier_segment(a, b, tolerance),
+            (Self::Segment(a), Self::Bezier(b)) => bezier_segment(b, a, tolerance)
+                .into_iter()
+                .map(Intersection::swap)
+                .collect(),
+            (Self::Bezier(a), Self::Bezier(b)) => bezier_bezier(a, b, tolerance),
+            (Self::Quad(_), _) | (_, Self::Quad(_)) => unreachable!(),
+        };
+
+        result.sort_by(|x, y| x.t_self.total_cmp(&y.t_self));
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn segment(a: (f64, f64), b: (f64, f64)) -> Curve<(f64, f64)> {
+        Curve::Segment(Segment::new(a, b))
+    }
+
+    fn bezier(a: (f64, f64), b: (f64, f64), c: (f64, f64), d: (f64, f64)) -> Curve<(f64, f64)> {
+        Curve::Bezier(Bezier::new(a, b, c, d))
+    }
+
+    fn kinds(hits: &[Intersection<(f64, f64)>]) -> Vec<IntersectionKind> {
+        hits.iter().map(|h| h.kind).collect()
+    }
+
+    #[test]
+    fn intersect_segments() {
+        let hits =
+            segment((0.0, 0.0), (2.0, 2.0)).intersections(&segment((0.0, 2.0), (2.0, 0.0)), 1e-9);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].point, (1.0, 1.0));
+        assert_eq!((hits[0].t_self, hits[0].t_other), (0.5, 0.5));
+        assert_eq!(hits[0].kind, IntersectionKind::Cross);
+
+        let hits =
+            segment((0.0, 0.0), (2.0, 0.0)).intersections(&segment((1.0, 0.0), (3.0, 0.0)), 1e-9);
+        assert_eq!(kinds(&hits), [
+            IntersectionKind::Overlap,
+            IntersectionKind::Overlap
+        ]);
+        assert_eq!((hits[0].t_self, hits[0].t_other), (0.5, 0.0));
+        assert_eq!((hits[1].t_self, hits[1].t_other), (1.0, 0.5));
+
+        let hits =
+            segment((0.0, 0.0), (1.0, 0.0)).intersections(&segment((0.0, 1.0), (1.0, 1.0)), 1e-9);
+        assert!(hits.is_empty());
+    }
+
+    #[test]
+    fn intersect_bezier_segment() {
+        // S shape curve crosses x axis three times
+        let b = bezier((0.0, -1.0), (1.0, 3.0), (2.0, -3.0), (3.0, 1.0));
+        let s = segment((-1.0, 0.0), (4.0, 0.0));
+        let hits = b.intersections(&s, 1e-9);
+        assert_eq!(kinds(&hits), [IntersectionKind::Cross; 3]);
+        for h in &hits {
+            assert!(h.point.1.abs() < 1e-9);
+            let p = s.at(h.t_other);
+            assert!(p.distance(&h.point) < 1e-9);
+        }
+
+        let swapped = s.intersections(&b, 1e-9);
+        assert_eq!(swapped.len(), 3);
+
+        // arch touches the line at top
+        let b = bezier((0.0, 0.0), (0.0, 4.0 / 3.0), (2.0, 4.0 / 3.0), (2.0, 0.0));
+        let hits = b.intersections(&segment((0.0, 1.0), (2.0, 1.0)), 1e-9);
+        assert_eq!(kinds(&hits), [IntersectionKind::Tangent]);
+        assert!((hits[0].t_self - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn intersect_beziers() {
+        let a = bezier((0.0, 0.0), (1.0, 2.0), (2.0, 2.0), (3.0, 0.0));
+        let b = bezier((0.0, 1.0), (1.0, -1.0), (2.0, -1.0), (3.0, 1.0));
+        let hits = a.intersections(&b, 1e-9);
+        assert_eq!(kinds(&hits), [IntersectionKind::Cross; 2]);
+        for h in &hits {
+            assert!(a.at(h.t_self).distance(&b.at(h.t_other)) < 1e-9);
+        }
+
+        let hits = a.intersections(&a.clone(), 1e-9);
+        assert_eq!(kinds(&hits), [IntersectionKind::Overlap; 2]);
+        assert_eq!((hits[0].t_self, hits[1].t_self), (0.0, 1.0));
+    }
+
+    #[test]
+    fn intersect_beziers_tangent() {
+        let a = bezier((0.0, 0.0), (0.0, 4.0 / 3.0), (2.0, 4.0 / 3.0), (2.0, 0.0));
+        let b = bezier((0.0, 2.0), (0.0, 2.0 / 3.0), (2.0, 2.0 / 3.0), (2.0, 2.0));
+        for tolerance in [1e-6, 1e-9] {
+            let hits = a.intersections(&b, tolerance);
+            assert_eq!(kinds(&hits), [IntersectionKind::Tangent]);
+            assert!((hits[0].t_self - 0.5).abs() < 1e-9);
+            assert!((hits[0].t_other - 0.5).abs() < 1e-9);
+            assert!(hits[0].point.distance(&(1.0, 1.0)) < 1e-9);
+        }
+    }
+
+    #[test]
+    fn intersect_beziers_near_coincide() {
+        // b = a + (0, k(t - 0.5)^2), which keeps within tolerance of a around t = 0.5 too long to
+        // be subdivided into candidates
+        let k = 1e-4;
+        let a = bezier((0.0, 0.0), (1.0, 2.0), (2.0, 2.0), (3.0, 0.0));
+        let b = bezier(
+            (0.0, 0.25 * k),
+            (1.0, 2.0 - k / 12.0),
+            (2.0, 2.0 - k / 12.0),
+            (3.0, 0.25 * k),
+        );
+
+        let hits = a.intersections(&b, 1e-9);
+        assert_eq!(kinds(&hits), [IntersectionKind::Tangent]);
+        assert!((hits[0].t_self - 0.5).abs() < 1e-6);
+        assert!(a.at(hits[0].t_self).distance(&b.at(hits[0].t_other)) <= 1e-9);
+    }
+
+    #[test]
+    fn intersect_arc_segment() {
+        let arc = Curve::Arc(Arc::new((0.0, 0.0), (10.0, 10.0), 0.0, 0.0, 180.0));
+        let l = segment((20.0, 5.0), (-20.0, 5.0));
+        let hits = arc.intersections(&l, 1e-9);
+        assert_eq!(kinds(&hits), [IntersectionKind::Cross; 2]);
+        assert!((hits[0].t_self - 1.0 / 6.0).abs() < 1e-9);
+        assert!((hits[1].t_self - 5.0 / 6.0).abs() < 1e-9);
+        assert!((hits[0].point.0 - libm::sqrt(75.0)).abs() < 1e-9);
+
+        let swapped = l.intersections(&arc, 1e-9);
+        assert_eq!(swapped.len(), 2);
+        assert!((swapped[0].t_other - 1.0 / 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn intersect_arc_pass_by() {
+        // cubic curves stay outside the arc of radius 100, closer than its rough approximation
+        let arc = Curve::Arc(Arc::new((0.0, 0.0), (100.0, 100.0), 0.0, 0.0, 90.0));
+        for gap in [1e-2, 5e-5] {
+            let r = 100.0 + gap;
+            let b = Arc::new((0.0, 0.0), (r, r), 0.0, 10.0, 70.0).to_cubics(1.0);
+            assert_eq!(b.len(), 1);
+            let b = Curve::Bezier(b[0].clone());
+
+            assert!(arc.intersections(&b, 1e-9).is_empty());
+            assert!(b.intersections(&arc, 1e-9).is_empty());
+        }
+
+        // touching the arc at the middle
+        let l = segment((100.0, -50.0), (100.0, 50.0));
+        let arc = Curve::Arc(Arc::new((0.0, 0.0), (100.0, 100.0), 0.0, -45.0, 90.0));
+        let hits = arc.intersections(&l, 1e-9);
+        assert_eq!(kinds(&hits), [IntersectionKind::Tangent]);
+        assert!((hits[0].t_self - 0.5).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,45 @@
+// Max iteration count when finding t at arc length.
+const MAX_ITERATION: usize = 64;
+
+// Find t in [0, 1] where the arc length of [0, t] equals to `s`, using Newton's method with
+// bisection fallback. `length_to` is the arc length from 0 to t, `speed` is the derivative of it.
+pub(super) fn t_at_length<L, S>(s: f64, total: f64, tolerance: f64, length_to: L, speed: S) -> f64
+where
+    L: Fn(f64) -> f64,
+    S: Fn(f64) -> f64,
+{
+    if s <= 0.0 || total <= 0.0 {
+        return 0.0;
+    }
+
+    if s >= total {
+        return 1.0;
+    }
+
+    let (mut lo, mut hi) = (0.0, 1.0);
+    let mut t = s / total;
+
+    for _ in 0..MAX_ITERATION {
+        let f = length_to(t) - s;
+        if libm::fabs(f) <= tolerance {
+            break;
+        }
+
+        if f > 0.0 {
+            hi = t;
+        } else {
+            lo = t;
+        }
+
+        let d = speed(t);
+        let next = t - f / d;
+
+        t = if d > 0.0 && lo < next && next < hi {
+            next
+        } else {
+            (lo + hi) / 2.0
+        };
+    }
+
+    t
+}
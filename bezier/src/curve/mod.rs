@@ -1,26 +1,59 @@
+mod arc;
 mod bezier;
+mod conic;
+mod quad;
 mod segment;
 mod nearest;
+mod length;
+mod intersect;
+mod classify;
+mod flatten;
+mod offset;
+mod reduce;
 
-pub use self::{bezier::Bezier, nearest::Nearest, segment::Segment};
-use crate::{CurvePoint, Point2D};
+pub use self::{
+    arc::{Arc, ArcParams},
+    bezier::Bezier,
+    classify::CubicKind,
+    conic::Conic,
+    intersect::{Intersection, IntersectionKind},
+    nearest::Nearest,
+    quad::QuadBezier,
+    segment::Segment,
+};
+use crate::{CurvePoint, Point2D, Rect};
 
+#[derive(Debug, Clone, PartialEq)]
 pub enum Curve<P> {
     Segment(Segment<P>),
+    Quad(QuadBezier<P>),
     Bezier(Bezier<P>),
+    Arc(Arc<P>),
 }
 
 impl<P: Point2D> Curve<P> {
+    /// Curve between two points, decided by the ctrl points between them: [Segment] if none,
+    /// [QuadBezier] if only one, and [Bezier] if both.
+    ///
+    /// If start point has an out arc, it's an [Arc] and in ctrl of end point is ignored. The arc
+    /// which should be a line, see [Arc::from_endpoints], becomes [Segment].
     pub fn new(start: &CurvePoint<P>, end: &CurvePoint<P>) -> Self {
         let sp = start.point().clone();
         let ep = end.point().clone();
 
+        if let Some(arc) = start.out_arc() {
+            return match arc.to_arc(&sp, &ep) {
+                Some(arc) => Self::Arc(arc),
+                None => Self::Segment(Segment::new(sp, ep)),
+            };
+        }
+
         match (start.out_ctrl(), end.in_ctrl()) {
             (Some(ctrl1), Some(ctrl2)) => {
                 Self::Bezier(Bezier::new(sp, ctrl1.into_owned(), ctrl2.into_owned(), ep))
             }
             (Some(ctrl), None) | (None, Some(ctrl)) => {
-                Self::Bezier(Bezier::new_quad(sp, ctrl.into_owned(), ep))
+                Self::Quad(QuadBezier::new(sp, ctrl.into_owned(), ep))
             }
             (None, None) => Self::Segment(Segment::new(sp, ep)),
         }
@@ -29,14 +62,119 @@ impl<P: Point2D> Curve<P> {
     pub fn at(&self, t: f64) -> P {
         match self {
             Self::Bezier(b) => b.at(t),
+            Self::Arc(a) => a.at(t),
+            Self::Quad(q) => q.at(t),
             Self::Segment(l) => l.at(t),
         }
     }
 
+    /// First derivative at t.
+    pub fn derivative(&self, t: f64) -> P {
+        match self {
+            Self::Bezier(b) => b.derivative(t),
+            Self::Arc(a) => a.derivative(t),
+            Self::Quad(q) => q.derivative(t),
+            Self::Segment(l) => l.derivative(),
+        }
+    }
+
+    /// Second derivative at t, zero for segment.
+    pub fn second_derivative(&self, t: f64) -> P {
+        match self {
+            Self::Bezier(b) => b.second_derivative(t),
+            Self::Arc(a) => a.second_derivative(t),
+            Self::Quad(q) => q.second_derivative(t),
+            Self::Segment(_) => P::from_xy(0.0, 0.0),
+        }
+    }
+
+    /// Unit tangent vector at t.
+    pub fn tangent(&self, t: f64) -> P {
+        match self {
+            Self::Bezier(b) => b.tangent(t),
+            Self::Arc(a) => a.tangent(t),
+            Self::Quad(q) => q.tangent(t),
+            Self::Segment(l) => l.tangent(),
+        }
+    }
+
+    /// Unit normal vector at t, which is the tangent rotated 90 degree counterclockwise.
+    pub fn normal(&self, t: f64) -> P {
+        match self {
+            Self::Bezier(b) => b.normal(t),
+            Self::Arc(a) => a.normal(t),
+            Self::Quad(q) => q.normal(t),
+            Self::Segment(l) => l.normal(),
+        }
+    }
+
+    /// Signed curvature at t, positive when the curve turns counterclockwise, zero for segment.
+    pub fn curvature(&self, t: f64) -> f64 {
+        match self {
+            Self::Bezier(b) => b.curvature(t),
+            Self::Arc(a) => a.curvature(t),
+            Self::Quad(q) => q.curvature(t),
+            Self::Segment(_) => 0.0,
+        }
+    }
+
+    /// Signed radius of curvature at t, infinite for segment.
+    pub fn radius_of_curvature(&self, t: f64) -> f64 {
+        match self {
+            Self::Bezier(b) => b.radius_of_curvature(t),
+            Self::Arc(a) => a.radius_of_curvature(t),
+            Self::Quad(q) => q.radius_of_curvature(t),
+            Self::Segment(_) => f64::INFINITY,
+        }
+    }
+
     pub fn nearest_to(&self, target: &P, allow_endpoint: bool) -> Option<Nearest<P>> {
         match self {
             Self::Bezier(b) => b.nearest_to(target, allow_endpoint),
+            Self::Arc(a) => a.nearest_to(target, allow_endpoint),
+            Self::Quad(q) => q.nearest_to(target, allow_endpoint),
             Self::Segment(l) => l.nearest_to(target, allow_endpoint),
         }
     }
+
+    /// Exact bounding box of the curve.
+    pub fn bounds(&self) -> Rect<P> {
+        match self {
+            Self::Bezier(b) => b.bounds(),
+            Self::Arc(a) => a.bounds(),
+            Self::Quad(q) => q.bounds(),
+            Self::Segment(l) => l.bounds(),
+        }
+    }
+
+    /// Bounding box of the curve's endpoints and control points, which is cheaper then
+    /// [Curve::bounds] but may be larger.
+    pub fn control_bounds(&self) -> Rect<P> {
+        match self {
+            Self::Bezier(b) => b.control_bounds(),
+            Self::Arc(a) => a.bounds(),
+            Self::Quad(q) => q.control_bounds(),
+            Self::Segment(l) => l.bounds(),
+        }
+    }
+
+    /// Arc length of the curve, `tolerance` is the max error allowed.
+    pub fn length(&self, tolerance: f64) -> f64 {
+        match self {
+            Self::Bezier(b) => b.length(tolerance),
+            Self::Arc(a) => a.length(tolerance),
+            Self::Quad(q) => q.length(tolerance),
+            Self::Segment(l) => l.length(),
+        }
+    }
+
+    /// Find t where the arc length from start point is `s`.
+    pub fn t_at_length(&self, s: f64, tolerance: f64) -> f64 {
+        match self {
+            Self::Bezier(b) => b.t_at_length(s, tolerance),
+            Self::Arc(a) => a.t_at_length(s, tolerance),
+            Self::Quad(q) => q.t_at_length(s, tolerance),
+            Self::Segment(l) => l.t_at_length(s),
+        }
+    }
 }
@@ -1,6 +1,6 @@
 use core::cmp::Ordering;
 
-use super::{Bezier, Point2D, Segment};
+use super::{Arc, Bezier, Conic, Point2D, QuadBezier, Segment};
 
 pub struct Nearest<P: Point2D> {
     pub index: usize,
@@ -74,6 +74,39 @@ impl<P: Point2D> Nearest<P> {
         }
     }
 
+    pub fn new_from_quad(line: &QuadBezier<P>, t: f64, target: &P) -> Self {
+        let point = line.at(t);
+        let distance = point.minus(target).length_from_origin();
+        Self {
+            index: 0,
+            t,
+            point,
+            distance,
+        }
+    }
+
+    pub fn new_from_arc(arc: &Arc<P>, t: f64, target: &P) -> Self {
+        let point = arc.at(t);
+        let distance = point.minus(target).length_from_origin();
+        Self {
+            index: 0,
+            t,
+            point,
+            distance,
+        }
+    }
+
+    pub fn new_from_conic(conic: &Conic<P>, t: f64, target: &P) -> Self {
+        let point = conic.at(t);
+        let distance = point.minus(target).length_from_origin();
+        Self {
+            index: 0,
+            t,
+            point,
+            distance,
+        }
+    }
+
     pub fn with_index(mut self, val: usize) -> Self {
         self.index = val;
         self
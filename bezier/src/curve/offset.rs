@@ -0,0 +1,330 @@
+use alloc::vec::Vec;
+
+use super::{Arc, Bezier, CubicKind, Curve, Segment};
+use crate::{Point2D, Poly, Root};
+
+// Max subdivision depth when approximating the offset of a piece.
+const MAX_DEPTH: usize = 16;
+
+// Count of points sampled inside each piece to measure approximation error.
+const ERROR_SAMPLES: usize = 16;
+
+// Split points too close to endpoints are ignored.
+const SPLIT_EPSILON: f64 = 1e-9;
+
+impl<P: Point2D> Segment<P> {
+    /// Parallel segment at signed distance `d`, positive distance is at the left side, which is
+    /// the direction of [Segment::normal].
+    pub fn offset(&self, d: f64) -> Segment<P> {
+        let n = self.normal().scale(d);
+
+        Segment::new(self.start().plus(&n), self.end().plus(&n))
+    }
+}
+
+impl<P: Point2D> Bezier<P> {
+    fn offset_point(&self, t: f64, d: f64) -> P {
+        self.parametric_function()(t).plus(&self.normal(t).scale(d))
+    }
+
+    // Derivative of the offset curve O(t) = B(t) + dN(t), which is B'(t)(1 - dκ(t)).
+    fn offset_derivative(&self, t: f64, d: f64) -> P {
+        let d1 = self.derivative(t);
+        let speed2 = d1.dot(&d1);
+
+        if speed2 == 0.0 {
+            return d1;
+        }
+
+        let k = d1.cross(&self.second_derivative(t)) / speed2;
+        d1.minus(&self.tangent(t).scale(d * k))
+    }
+
+    // Cubic with the same endpoints and derivatives as the offset curve in [t0, t1].
+    fn offset_hermite(&self, t0: f64, t1: f64, d: f64) -> Self {
+        let h = (t1 - t0) / 3.0;
+        let start = self.offset_point(t0, d);
+        let end = self.offset_point(t1, d);
+
+        let ctrl1 = start.plus(&self.offset_derivative(t0, d).scale(h));
+        let ctrl2 = end.minus(&self.offset_derivative(t1, d).scale(h));
+
+        Self::new(start, ctrl1, ctrl2, end)
+    }
+
+    fn offset_recursive(
+        &self, t0: f64, t1: f64, d: f64, tolerance: f64, depth: usize, result: &mut Vec<Self>,
+    ) {
+        let approx = self.offset_hermite(t0, t1, d);
+
+        // distance to the offset point of the same parameter bounds distance to the offset curve
+        let error = (1..=ERROR_SAMPLES)
+            .map(|i| {
+                let u = i as f64 / (ERROR_SAMPLES + 1) as f64;
+                approx.parametric_function()(u).distance(&self.offset_point(t0 + (t1 - t0) * u, d))
+            })
+            .fold(0.0, f64::max);
+
+        if depth == 0 || error <= tolerance {
+            result.push(approx);
+            return;
+        }
+
+        let mid = (t0 + t1) / 2.0;
+        self.offset_recursive(t0, mid, d, tolerance, depth - 1, result);
+        self.offset_recursive(mid, t1, d, tolerance, depth - 1, result);
+    }
+
+    // Parameters where the offset curve has a cusp, the radius of curvature equals `d` at the side
+    // of its center, so B'(t)(1 - dκ(t)) vanishes. Squared, |B'|^6 = d^2 (B' x B'')^2.
+    fn offset_cusps(&self, d: f64) -> Vec<f64> {
+        let [a, b, c, _] = self.parametric_function_coefficients();
+        let d1 = |a: f64, b: f64, c: f64| Poly::from_iter([3.0 * a, 2.0 * b, c]);
+        let d2 = |a: f64, b: f64| Poly::from_iter([6.0 * a, 2.0 * b]);
+        let (dx, dy) = (d1(a.x(), b.x(), c.x()), d1(a.y(), b.y(), c.y()));
+        let (ddx, ddy) = (d2(a.x(), b.x()), d2(a.y(), b.y()));
+
+        let speed2 = dx.mul(&dx).add(&dy.mul(&dy));
+        let cross = dx.mul(&ddy).sub(&dy.mul(&ddx));
+        let poly = speed2
+            .mul(&speed2)
+            .mul(&speed2)
+            .sub(&cross.mul(&cross).mul(&Poly::from_iter([d * d])));
+
+        match poly.real_roots_in(0.0..=1.0) {
+            Root::Roots(roots) => roots
+                .into_iter()
+                .filter(|t| d * cross.eval(*t) > 0.0)
+                .collect(),
+            Root::None | Root::Any => Vec::new(),
+        }
+    }
+
+    // Parameters where the curve should be split before offsetting: cusps, self-intersection and
+    // inflections, or turning back points of a degenerated line, and cusps of the offset curve.
+    fn offset_split_points(&self, d: f64) -> Vec<f64> {
+        let mut ts = match self.classify() {
+            CubicKind::Cusp { t } => vec![t],
+            CubicKind::Loop { t1, t2 } => vec![t1, t2],
+            CubicKind::Line => self.extrema(),
+            _ => Vec::new(),
+        };
+        ts.extend(self.inflections());
+        ts.extend(self.offset_cusps(d));
+
+        ts.retain(|t| SPLIT_EPSILON < *t && *t < 1.0 - SPLIT_EPSILON);
+        ts.sort_by(f64::total_cmp);
+        ts.dedup_by(|a, b| *a - *b <= SPLIT_EPSILON);
+        ts
+    }
+
+    /// Approximate the parallel curve at signed distance `d` with a sequence of cubic curves,
+    /// the error to the real offset curve is within `tolerance`.
+    ///
+    /// Positive distance is at the left side, which is the direction of [Bezier::normal]. The
+    /// curve is split at cusps, self-intersection and inflections first, so the pieces may be
+    /// disconnected where the source curve has a cusp. It's also split where the offset curve has
+    /// a cusp, that is where radius of curvature equals `|d|` at the side of the offset.
+    ///
+    /// Returns empty if the curve is a single point.
+    pub fn offset(&self, d: f64, tolerance: f64) -> Vec<Self> {
+        if self.classify() == CubicKind::Point {
+            return Vec::new();
+        }
+
+        let mut result = Vec::new();
+
+        let mut ts = vec![0.0];
+        ts.extend(self.offset_split_points(d));
+        ts.push(1.0);
+
+        for w in ts.windows(2) {
+            self.offset_recursive(w[0], w[1], d, tolerance, MAX_DEPTH, &mut result);
+        }
+
+        result
+    }
+}
+
+impl<P: Point2D> Arc<P> {
+    // Offset of a circular arc is the concentric one, if its radius is still positive.
+    fn offset_circular(&self, d: f64) -> Option<Self> {
+        let r = self.radii.x();
+        if r != self.radii.y() {
+            return None;
+        }
+
+        // left side of a counterclockwise arc is towards center
+        let r = if self.sweep_angle > 0.0 { r - d } else { r + d };
+
+        (r > 0.0).then(|| {
+            Self::new(
+                self.center.clone(),
+                P::from_xy(r, r),
+                self.rotation,
+                self.start_angle,
+                self.sweep_angle,
+            )
+        })
+    }
+}
+
+impl<P: Point2D> Curve<P> {
+    /// Approximate the parallel curve at signed distance `d`, positive distance is at the left
+    /// side. See [Bezier::offset] for details, quadratic curve is offset as cubic.
+    ///
+    /// Offset of a circular arc is exact, elliptical arc is converted to cubic curves first.
+    pub fn offset(&self, d: f64, tolerance: f64) -> Vec<Self> {
+        match self {
+            Self::Quad(q) => q
+                .to_cubic()
+                .offset(d, tolerance)
+                .into_iter()
+                .map(Self::Bezier)
+                .collect(),
+            Self::Bezier(b) => b
+                .offset(d, tolerance)
+                .into_iter()
+                .map(Self::Bezier)
+                .collect(),
+            // half of the tolerance is for approximating the arc, and half for the offset of it
+            Self::Arc(a) => match a.offset_circular(d) {
+                Some(arc) => vec![Self::Arc(arc)],
+                None => a
+                    .to_cubics(tolerance / 2.0)
+                    .iter()
+                    .flat_map(|b| b.offset(d, tolerance / 2.0))
+                    .map(Self::Bezier)
+                    .collect(),
+            },
+            Self::Segment(l) => vec![Self::Segment(l.offset(d))],
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // max distance between the offset pieces and the exact offset curve, both sampled densely
+    fn deviation(b: &Bezier<(f64, f64)>, pieces: &[Bezier<(f64, f64)>], d: f64) -> f64 {
+        let n = 4096;
+        let exact: Vec<_> = (0..=n)
+            .map(|i| b.offset_point(i as f64 / n as f64, d))
+            .collect();
+        let approx: Vec<_> = pieces
+            .iter()
+            .flat_map(|p| (0..=64).map(move |i| p.at(i as f64 / 64.0)))
+            .collect();
+
+        let to_segment = |p: &(f64, f64), start: &(f64, f64), end: &(f64, f64)| {
+            let v = end.minus(start);
+            let len2 = v.dot(&v);
+            let t = if len2 == 0.0 {
+                0.0
+            } else {
+                (p.minus(start).dot(&v) / len2).clamp(0.0, 1.0)
+            };
+            p.distance(&start.plus(&v.scale(t)))
+        };
+
+        // both polylines go along the curve, so only segments around the last nearest one are
+        // checked
+        let one_side = |from: &[(f64, f64)], to: &[(f64, f64)]| {
+            let mut k: usize = 0;
+            from.iter()
+                .map(|p| {
+                    let range = k.saturating_sub(64)..(k + 64).min(to.len() - 1);
+                    let (i, dist) = range
+                        .map(|i| (i, to_segment(p, &to[i], &to[i + 1])))
+                        .min_by(|x, y| x.1.total_cmp(&y.1))
+                        .unwrap();
+                    k = i;
+                    dist
+                })
+                .fold(0.0, f64::max)
+        };
+
+        f64::max(one_side(&approx, &exact), one_side(&exact, &approx))
+    }
+
+    #[test]
+    fn offset_elliptical_arc_within_tolerance() {
+        // radius of curvature is at least 50, so points of the offset curve are exactly `|d|` away
+        // from the arc
+        let arc = Arc::new((0.0, 0.0), (200.0, 100.0), 30.0, 200.0, -300.0);
+
+        for d in [5.0, -5.0, 40.0] {
+            for tolerance in [1e-2, 1e-4, 1e-6] {
+                let pieces = Curve::Arc(arc.clone()).offset(d, tolerance);
+                for piece in &pieces {
+                    for i in 0..=32 {
+                        let p = piece.at(i as f64 / 32.0);
+                        let error = (arc.nearest_to(&p, true).unwrap().distance - d.abs()).abs();
+                        assert!(error <= tolerance, "{error} > {tolerance} at {d}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn offset_segment() {
+        let s = Segment::new((0.0, 0.0), (10.0, 0.0));
+        assert_eq!(s.offset(2.0), Segment::new((0.0, 2.0), (10.0, 2.0)));
+        assert_eq!(s.offset(-2.0), Segment::new((0.0, -2.0), (10.0, -2.0)));
+    }
+
+    #[test]
+    fn offset_within_tolerance() {
+        let b = Bezier::new((0.0, 0.0), (0.0, 100.0), (300.0, 100.0), (300.0, 0.0));
+
+        for d in [5.0, -5.0, 20.0, -20.0, -60.0] {
+            let pieces = b.offset(d, 0.01);
+            assert!(deviation(&b, &pieces, d) <= 0.01);
+
+            // pieces are connected
+            for w in pieces.windows(2) {
+                assert!(w[0].end.distance(&w[1].start) < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn offset_split_loop_and_cusp() {
+        let b = Bezier::new((0.0, 0.0), (30.0, 30.0), (-10.0, 30.0), (20.0, 0.0));
+        let (t1, t2) = b.self_intersection().unwrap();
+
+        let pieces = b.offset(1.0, 0.01);
+        assert!(pieces.len() >= 3);
+        assert!(deviation(&b, &pieces, 1.0) <= 0.01);
+        assert!(pieces
+            .iter()
+            .any(|p| p.start.distance(&b.offset_point(t1, 1.0)) < 1e-9));
+        assert!(pieces
+            .iter()
+            .any(|p| p.start.distance(&b.offset_point(t2, 1.0)) < 1e-9));
+
+        let b = Bezier::new((0.0, 0.0), (20.0, 20.0), (0.0, 20.0), (20.0, 0.0));
+        assert!(!b.offset(1.0, 0.01).is_empty());
+
+        let p = (1.0, 1.0);
+        assert!(Bezier::new(p, p, p, p).offset(1.0, 0.01).is_empty());
+    }
+
+    #[test]
+    fn offset_split_offset_cusp() {
+        // radius of curvature near both ends is smaller than 60 at the right side
+        let b = Bezier::new((0.0, 0.0), (0.0, 100.0), (300.0, 100.0), (300.0, 0.0));
+        let cusps = b.offset_cusps(-60.0);
+        assert_eq!(cusps.len(), 2);
+        assert!(b.offset_cusps(60.0).is_empty());
+
+        let pieces = b.offset(-60.0, 0.01);
+        for t in cusps {
+            assert!(b.offset_derivative(t, -60.0).length_from_origin() < 1e-6);
+            let cusp = b.offset_point(t, -60.0);
+            assert!(pieces.iter().any(|p| p.start.distance(&cusp) < 1e-9));
+        }
+    }
+}
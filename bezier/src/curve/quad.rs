@@ -0,0 +1,244 @@
+use alloc::vec::Vec;
+
+use super::{length, Bezier, Nearest};
+use crate::{integrate, Point2D, Poly, Rect, Root};
+
+/// Quadratic bezier curve, which has only one control point.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuadBezier<P> {
+    pub start: P,
+    pub ctrl: P,
+    pub end: P,
+}
+
+impl<P> QuadBezier<P> {
+    pub fn new(start: P, ctrl: P, end: P) -> Self {
+        Self { start, ctrl, end }
+    }
+}
+
+impl<P: Point2D> QuadBezier<P> {
+    // [a, b, c] of a t^2 + b t + c
+    pub(crate) fn parametric_function_coefficients(&self) -> [P; 3] {
+        [
+            self.start.minus(&self.ctrl.scale(2.0)).plus(&self.end),
+            self.ctrl.minus(&self.start).scale(2.0),
+            self.start.clone(),
+        ]
+    }
+
+    pub fn parametric_function(&self) -> impl Fn(f64) -> P {
+        let [a, b, c] = self.parametric_function_coefficients();
+
+        move |t| a.scale(t * t).plus(&b.scale(t)).plus(&c)
+    }
+
+    pub fn at(&self, t: f64) -> P {
+        assert!((0.0..=1.0).contains(&t));
+        self.parametric_function()(t)
+    }
+
+    /// The same curve as a cubic bezier, by degree elevation.
+    pub fn to_cubic(&self) -> Bezier<P> {
+        Bezier::new_quad(self.start.clone(), self.ctrl.clone(), self.end.clone())
+    }
+
+    /// First derivative B'(t).
+    pub fn derivative(&self, t: f64) -> P {
+        let [a, b, _] = self.parametric_function_coefficients();
+
+        a.scale(2.0 * t).plus(&b)
+    }
+
+    /// Second derivative B''(t), which is constant.
+    pub fn second_derivative(&self, _t: f64) -> P {
+        let [a, ..] = self.parametric_function_coefficients();
+
+        a.scale(2.0)
+    }
+
+    /// Unit tangent vector at t.
+    ///
+    /// Where the first derivative is zero, which is when control point coincides with endpoint,
+    /// direction of the second derivative is used.
+    pub fn tangent(&self, t: f64) -> P {
+        let d = self.derivative(t);
+
+        if d.length_from_origin() != 0.0 {
+            d.normalize()
+        } else {
+            self.second_derivative(t).normalize()
+        }
+    }
+
+    /// Unit normal vector at t, which is the tangent rotated 90 degree counterclockwise.
+    pub fn normal(&self, t: f64) -> P {
+        let tangent = self.tangent(t);
+
+        P::from_xy(-tangent.y(), tangent.x())
+    }
+
+    /// Signed curvature at t, positive when the curve turns counterclockwise.
+    ///
+    /// Not finite where the first derivative is zero.
+    pub fn curvature(&self, t: f64) -> f64 {
+        let d1 = self.derivative(t);
+        let d2 = self.second_derivative(t);
+        let speed = d1.length_from_origin();
+
+        d1.cross(&d2) / (speed * speed * speed)
+    }
+
+    /// Signed radius of curvature at t, which is reciprocal of [QuadBezier::curvature].
+    pub fn radius_of_curvature(&self, t: f64) -> f64 {
+        1.0 / self.curvature(t)
+    }
+
+    pub fn split_at(&self, t: f64) -> (Self, Self) {
+        let p = self.at(t);
+
+        let left = Self::new(
+            self.start.clone(),
+            self.start.plus(&self.ctrl.minus(&self.start).scale(t)),
+            p.clone(),
+        );
+        let right = Self::new(
+            p,
+            self.ctrl.plus(&self.end.minus(&self.ctrl).scale(t)),
+            self.end.clone(),
+        );
+
+        (left, right)
+    }
+
+    // d/dt |B(t) - target|^2 / 2, which is a cubic polynomial
+    fn distance_derivative(&self, target: &P) -> Poly {
+        let [a, b, c] = self.parametric_function_coefficients();
+        let ct = c.minus(target);
+
+        [
+            2.0 * a.dot(&a),
+            3.0 * a.dot(&b),
+            b.dot(&b) + 2.0 * a.dot(&ct),
+            b.dot(&ct),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    pub fn nearest_to(&self, target: &P, allow_endpoint: bool) -> Option<Nearest<P>> {
+        // We only need real root between (0, 1) because we add endpoints according to param
+        let roots = match self.distance_derivative(target).real_roots_in(0.0..=1.0) {
+            Root::Roots(roots) => roots,
+            Root::None | Root::Any => Vec::new(),
+        };
+
+        let endpoints = if allow_endpoint {
+            Some(0.0).into_iter().chain(Some(1.0))
+        } else {
+            None.into_iter().chain(None)
+        };
+
+        roots
+            .into_iter()
+            .filter(|t| 0.0 < *t && *t < 1.0)
+            .chain(endpoints)
+            .map(|t| Nearest::new_from_quad(self, t, target))
+            .min()
+    }
+
+    /// Sorted t values in [0, 1] where x'(t) or y'(t) is zero.
+    pub fn extrema(&self) -> Vec<f64> {
+        let [a, b, _] = self.parametric_function_coefficients();
+
+        let mut roots: Vec<_> = [(a.x(), b.x()), (a.y(), b.y())]
+            .into_iter()
+            .filter(|(a, _)| *a != 0.0)
+            .map(|(a, b)| -b / (2.0 * a))
+            .filter(|t| (0.0..=1.0).contains(t))
+            .collect();
+        roots.sort_by(f64::total_cmp);
+        roots.dedup();
+
+        roots
+    }
+
+    /// Bounding box of the three control points, which always contains the curve.
+    pub fn control_bounds(&self) -> Rect<P> {
+        Rect::from_points([&self.start, &self.ctrl, &self.end]).unwrap()
+    }
+
+    /// Exact bounding box of the curve.
+    pub fn bounds(&self) -> Rect<P> {
+        let mut rect = Rect::new(self.start.clone(), self.end.clone());
+
+        let f = self.parametric_function();
+        for t in self.extrema() {
+            rect.include(&f(t));
+        }
+
+        rect
+    }
+
+    fn speed_function(&self) -> impl Fn(f64) -> f64 {
+        let [a, b, _] = self.parametric_function_coefficients();
+
+        move |t| a.scale(2.0 * t).plus(&b).length_from_origin()
+    }
+
+    /// Arc length of the curve, `tolerance` is the max error allowed.
+    pub fn length(&self, tolerance: f64) -> f64 {
+        integrate(self.speed_function(), 0.0, 1.0, tolerance)
+    }
+
+    /// Find t where the arc length from start point is `s`, clamped to [0, 1].
+    ///
+    /// The arc length of result t differs from `s` by at most `tolerance`.
+    pub fn t_at_length(&self, s: f64, tolerance: f64) -> f64 {
+        let speed = self.speed_function();
+        let total = integrate(&speed, 0.0, 1.0, tolerance / 4.0);
+
+        length::t_at_length(
+            s,
+            total,
+            tolerance,
+            |t| integrate(&speed, 0.0, t, tolerance / 4.0),
+            &speed,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn quad_same_as_cubic() {
+        let q = QuadBezier::new((0.0, 0.0), (10.0, 20.0), (30.0, 0.0));
+        let c = q.to_cubic();
+
+        for i in 0..=8 {
+            let t = i as f64 / 8.0;
+            assert!(q.at(t).distance(&c.at(t)) < 1e-12);
+            assert!(q.derivative(t).distance(&c.derivative(t)) < 1e-12);
+            assert!((q.curvature(t) - c.curvature(t)).abs() < 1e-12);
+        }
+
+        assert!((q.length(1e-9) - c.length(1e-9)).abs() < 1e-9);
+        assert_eq!(q.bounds(), c.bounds());
+    }
+
+    #[test]
+    fn quad_split_and_nearest() {
+        let q = QuadBezier::new((0.0, 0.0), (10.0, 20.0), (30.0, 0.0));
+        let (left, right) = q.split_at(0.3);
+        assert!(left.at(0.5).distance(&q.at(0.15)) < 1e-12);
+        assert!(right.at(0.5).distance(&q.at(0.65)) < 1e-12);
+
+        let target = (12.0, 20.0);
+        let nearest = q.nearest_to(&target, true).unwrap();
+        let cubic = q.to_cubic().nearest_to(&target, true).unwrap();
+        assert!((nearest.t - cubic.t).abs() < 1e-9);
+        assert!((nearest.distance - cubic.distance).abs() < 1e-9);
+    }
+}
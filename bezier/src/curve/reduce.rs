@@ -0,0 +1,163 @@
+use alloc::vec::Vec;
+
+use super::{Bezier, Curve, QuadBezier};
+use crate::Point2D;
+
+// Relative tolerance to check if a cubic is an elevated quadratic.
+const QUAD_EPSILON: f64 = 1e-9;
+
+// Max distance between a cubic and the quadratic approximation from `midpoint_quad` is
+// sqrt(3) / 36 * |a|, where a is the coefficient of t^3.
+const QUAD_ERROR_FACTOR: f64 = 0.048_112_522_432_468_816;
+
+// Max count of quadratics a single cubic is reduced to.
+const MAX_QUADS: usize = 1 << 16;
+
+// Min tolerance relative to size of the control point bounds. Smaller ones, including zero,
+// negative and NaN, are raised to it.
+const MIN_RELATIVE_TOLERANCE: f64 = 1e-9;
+
+impl<P: Point2D> Bezier<P> {
+    // Quadratic whose control point is the average of the two extrapolated from each end.
+    fn midpoint_quad(&self) -> QuadBezier<P> {
+        let ctrl = self
+            .ctrl1
+            .plus(&self.ctrl2)
+            .scale(3.0)
+            .minus(&self.start)
+            .minus(&self.end)
+            .scale(0.25);
+
+        QuadBezier::new(self.start.clone(), ctrl, self.end.clone())
+    }
+
+    /// The quadratic curve, if this is exactly a degree elevated one, such as created by
+    /// [Bezier::new_quad] or [QuadBezier::to_cubic]. This is the reverse of the elevation.
+    pub fn as_quad(&self) -> Option<QuadBezier<P>> {
+        let [a, ..] = self.parametric_function_coefficients();
+        let scale = [&self.ctrl1, &self.ctrl2, &self.end]
+            .into_iter()
+            .map(|p| p.distance(&self.start))
+            .fold(0.0, f64::max);
+
+        (a.length_from_origin() <= QUAD_EPSILON * scale).then(|| self.midpoint_quad())
+    }
+
+    /// Approximate the cubic curve with a chain of quadratic curves, the distance between them is
+    /// within `tolerance`.
+    ///
+    /// The curve is split evenly into the fewest pieces that the error bound allows, so an
+    /// elevated quadratic becomes exactly one piece. `tolerance` is at least 1e-9 of the control
+    /// point bounds size, smaller or non-positive ones are raised to it.
+    pub fn to_quads(&self, tolerance: f64) -> Vec<QuadBezier<P>> {
+        let bounds = self.control_bounds();
+        let size = f64::max(bounds.width(), bounds.height());
+        let tolerance = f64::max(tolerance, size * MIN_RELATIVE_TOLERANCE);
+
+        let [a, ..] = self.parametric_function_coefficients();
+
+        // error of each piece decreases with n^3 when split into n pieces
+        let error = QUAD_ERROR_FACTOR * a.length_from_origin();
+        let n = (libm::ceil(libm::cbrt(error / tolerance)) as usize).clamp(1, MAX_QUADS);
+
+        let mut quads = Vec::with_capacity(n);
+        let mut remain = self.clone();
+
+        for i in 0..n - 1 {
+            let (left, right) = remain.split_at(1.0 / (n - i) as f64);
+            quads.push(left.midpoint_quad());
+            remain = right;
+        }
+        quads.push(remain.midpoint_quad());
+
+        quads
+    }
+}
+
+impl<P: Point2D> Curve<P> {
+    /// Approximate the curve with a chain of quadratic curves within `tolerance`, segment
+    /// becomes one quadratic with control point at its middle.
+    pub fn to_quads(&self, tolerance: f64) -> Vec<QuadBezier<P>> {
+        match self {
+            Self::Bezier(b) => b.to_quads(tolerance),
+            Self::Quad(q) => vec![q.clone()],
+            Self::Arc(a) => a
+                .to_cubics(tolerance / 2.0)
+                .iter()
+                .flat_map(|b| b.to_quads(tolerance / 2.0))
+                .collect(),
+            Self::Segment(l) => vec![QuadBezier::new(
+                l.start().clone(),
+                l.at(0.5),
+                l.end().clone(),
+            )],
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Arc;
+
+    #[test]
+    fn quad_round_trip() {
+        let b = Bezier::new_quad((0.0, 0.0), (3.0, 6.0), (6.0, 0.0));
+
+        let quad = b.as_quad().unwrap();
+        assert!(quad.ctrl.distance(&(3.0, 6.0)) < 1e-12);
+        assert_eq!(b.to_quads(1e-6).len(), 1);
+
+        let b = Bezier::new((0.0, 0.0), (0.0, 10.0), (30.0, 10.0), (30.0, 0.0));
+        assert!(b.as_quad().is_none());
+    }
+
+    #[test]
+    fn to_quads_within_tolerance() {
+        let b = Bezier::new((0.0, 0.0), (0.0, 100.0), (300.0, 100.0), (300.0, -100.0));
+
+        for tolerance in [1.0, 0.1, 0.01] {
+            let quads = b.to_quads(tolerance);
+            assert_eq!(quads.first().unwrap().start, b.start);
+            assert_eq!(quads.last().unwrap().end, b.end);
+
+            for quad in &quads {
+                for i in 0..=16 {
+                    let d = b.nearest_to(&quad.at(i as f64 / 16.0), true).unwrap();
+                    assert!(d.distance <= tolerance, "{} > {tolerance}", d.distance);
+                }
+            }
+        }
+
+        assert!(b.to_quads(0.01).len() > b.to_quads(1.0).len());
+    }
+
+    #[test]
+    fn to_quads_min_tolerance() {
+        let b = Bezier::new((0.0, 0.0), (0.0, 100.0), (300.0, 100.0), (300.0, -100.0));
+        let min = b.to_quads(300.0 * MIN_RELATIVE_TOLERANCE);
+        assert!(1 < min.len() && min.len() < MAX_QUADS);
+
+        for tolerance in [0.0, -1.0, f64::NAN] {
+            assert_eq!(b.to_quads(tolerance), min);
+        }
+
+        // a point is a single quadratic
+        let p = (1.0, 1.0);
+        assert_eq!(Bezier::new(p, p, p, p).to_quads(0.0).len(), 1);
+    }
+
+    #[test]
+    fn arc_to_quads_within_tolerance() {
+        let arc = Arc::new((0.0, 0.0), (200.0, 100.0), 30.0, 200.0, -300.0);
+
+        for tolerance in [1e-2, 1e-4, 1e-6] {
+            for quad in Curve::Arc(arc.clone()).to_quads(tolerance) {
+                for i in 0..=16 {
+                    let d = arc.nearest_to(&quad.at(i as f64 / 16.0), true).unwrap();
+                    assert!(d.distance <= tolerance, "{} > {tolerance}", d.distance);
+                }
+            }
+        }
+    }
+}
@@ -1,5 +1,7 @@
 use super::{Nearest, Point2D};
+use crate::Rect;
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct Segment<P> {
     start: P,
     end: P,
@@ -9,6 +11,14 @@ impl<P> Segment<P> {
     pub fn new(start: P, end: P) -> Self {
         Self { start, end }
     }
+
+    pub fn start(&self) -> &P {
+        &self.start
+    }
+
+    pub fn end(&self) -> &P {
+        &self.end
+    }
 }
 
 impl<P: Point2D> Segment<P> {
@@ -27,6 +37,42 @@ impl<P: Point2D> Segment<P> {
         self.parametric_function()(t)
     }
 
+    /// First derivative, which is constant for a segment.
+    pub fn derivative(&self) -> P {
+        self.end.minus(&self.start)
+    }
+
+    /// Unit tangent vector, from start to end.
+    pub fn tangent(&self) -> P {
+        self.derivative().normalize()
+    }
+
+    /// Unit normal vector, which is the tangent rotated 90 degree counterclockwise.
+    pub fn normal(&self) -> P {
+        let tangent = self.tangent();
+
+        P::from_xy(-tangent.y(), tangent.x())
+    }
+
+    pub fn length(&self) -> f64 {
+        self.start.distance(&self.end)
+    }
+
+    /// Find t where the distance from start point is `s`, clamped to [0, 1].
+    pub fn t_at_length(&self, s: f64) -> f64 {
+        let length = self.length();
+
+        if length == 0.0 {
+            return 0.0;
+        }
+
+        (s / length).clamp(0.0, 1.0)
+    }
+
+    pub fn bounds(&self) -> Rect<P> {
+        Rect::new(self.start.clone(), self.end.clone())
+    }
+
     fn distance_derivative_coefficients(&self, target: &P) -> [f64; 2] {
         let [a, b] = self.parametric_function_coefficients();
 
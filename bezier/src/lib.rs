@@ -21,24 +21,31 @@
 //! If neither control point exists, then the two [CurvePoint] define a line [Segment]. Otherwise,
 //! it is a [Bezier] curve.
 //!
+//! Instead of an outgoing control point, a [CornerPoint] can have an outgoing [ArcParams], then
+//! the curve to the next point is an elliptical [Arc], which follows its endpoints when they move.
+//!
 //! ## Create
 //!
 //! You can create [Shape] using the concept above. a iterator of [CurvePoint] can be collected into
 //! [Shape].
 //!
-//! Or, you can use the ShapePainter, to construct a [Shape] in a way more focus on each curve other
-//! than each point.
+//! Or, you can use the [ShapePainter], to construct a [Shape] in a way more focus on each curve
+//! other than each point.
 //!
 //! Besides, you can parse a SVG path command string into a [Shape] using the
 //! [Shape::parse_svg_path].
 //!
+//! A [Shape] has only one contour, use [Path] for shapes with holes or several separated parts,
+//! each [Shape] in it is a subpath.
+//!
 //! ## Render
 //!
-//! Alley is designed for editing not rendering, it's out of scope.
+//! Alley is designed for editing not rendering, a full renderer is out of scope.
 //!
-//! You can refer to ...
+//! For headless previews and tests, [Shape::rasterize] and [Path::rasterize] fill them into an
+//! anti-aliased 8-bit coverage [Mask], and [Rasterizer] can draw several of them into one.
 
-#![cfg_attr(not(test), no_std)]
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
 
 #[macro_use]
 extern crate alloc;
@@ -46,6 +53,13 @@ extern crate alloc;
 mod curve;
 mod point;
 mod shape;
+mod path;
 mod math;
+mod raster;
+mod rect;
+mod svg;
+mod transform;
 
-pub use self::{curve::*, math::*, point::*, shape::*};
+pub use self::{
+    curve::*, math::*, path::*, point::*, raster::*, rect::*, shape::*, svg::*, transform::*,
+};
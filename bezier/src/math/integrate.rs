@@ -0,0 +1,66 @@
+// Nodes and weights of 5 points Gauss-Legendre quadrature in [-1, 1]
+const GAUSS_LEGENDRE_5: [(f64, f64); 5] = [
+    (0.0, 0.568_888_888_888_888_9),
+    (-0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+    (0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+    (-0.906_179_845_938_664, 0.236_926_885_056_189_1),
+    (0.906_179_845_938_664, 0.236_926_885_056_189_1),
+];
+
+// Max recursion depth of adaptive integration, limits the smallest sub interval to 2^-20 of the
+// origin one.
+const MAX_DEPTH: usize = 20;
+
+fn gauss_legendre<F: Fn(f64) -> f64>(f: &F, a: f64, b: f64) -> f64 {
+    let half = (b - a) / 2.0;
+    let mid = (a + b) / 2.0;
+
+    GAUSS_LEGENDRE_5
+        .iter()
+        .map(|(x, w)| w * f(mid + half * x))
+        .sum::<f64>()
+        * half
+}
+
+fn adaptive<F: Fn(f64) -> f64>(f: &F, a: f64, b: f64, whole: f64, tol: f64, depth: usize) -> f64 {
+    let mid = (a + b) / 2.0;
+    let left = gauss_legendre(f, a, mid);
+    let right = gauss_legendre(f, mid, b);
+
+    if depth == 0 || libm::fabs(left + right - whole) <= tol {
+        return left + right;
+    }
+
+    adaptive(f, a, mid, left, tol / 2.0, depth - 1)
+        + adaptive(f, mid, b, right, tol / 2.0, depth - 1)
+}
+
+// Integrate f over [a, b] using adaptive Gauss-Legendre quadrature, sub intervals are split
+// until the estimated error is less then `tol`.
+pub(crate) fn integrate<F: Fn(f64) -> f64>(f: F, a: f64, b: f64, tol: f64) -> f64 {
+    if a == b {
+        return 0.0;
+    }
+
+    let whole = gauss_legendre(&f, a, b);
+    adaptive(&f, a, b, whole, tol, MAX_DEPTH)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn integrate_poly() {
+        // ∫ x^3 dx from 0 to 2 = 4
+        let v = integrate(|x| x * x * x, 0.0, 2.0, 1e-12);
+        assert!((v - 4.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn integrate_sqrt() {
+        // ∫ sqrt(x) dx from 0 to 1 = 2/3
+        let v = integrate(libm::sqrt, 0.0, 1.0, 1e-9);
+        assert!((v - 2.0 / 3.0).abs() < 1e-8, "{v}");
+    }
+}
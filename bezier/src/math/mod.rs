@@ -1,4 +1,6 @@
 mod poly;
 mod sturm;
+mod integrate;
 
+pub(crate) use self::integrate::integrate;
 pub use self::{poly::*, sturm::*};
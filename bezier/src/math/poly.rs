@@ -1,11 +1,21 @@
 use alloc::vec::Vec;
 use core::{
-    iter::repeat,
-    ops::{Range, RangeInclusive},
+    iter::{repeat, repeat_n},
+    ops::RangeInclusive,
 };
 
 use super::SturmSeq;
 
+// Max iteration count of newton method from a single start point.
+const MAX_NEWTON_ITERATION: usize = 64;
+
+// Newton step smaller than this many ulps of x is treated as converged.
+const NEWTON_EPSILON: f64 = 4.0 * f64::EPSILON;
+
+// Max count of newton start points tried in one isolated interval, more would only split the
+// interval below float resolution.
+const MAX_NEWTON_START: usize = 1 << 10;
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Poly {
     c: Vec<f64>,
@@ -57,6 +67,41 @@ impl Poly {
             .collect()
     }
 
+    /// Antiderivative whose constant term is zero.
+    pub fn integral(&self) -> Poly {
+        let degree = self.degree();
+        self.coefficients()
+            .iter()
+            .enumerate()
+            .map(|(i, c)| c / (degree - i + 1) as f64)
+            .chain(Some(0.0))
+            .collect()
+    }
+
+    pub fn add(&self, rhs: &Poly) -> Poly {
+        let len = self.c.len().max(rhs.c.len());
+        let a = repeat_n(0.0, len - self.c.len()).chain(self.c.iter().copied());
+        let b = repeat_n(0.0, len - rhs.c.len()).chain(rhs.c.iter().copied());
+
+        a.zip(b).map(|(a, b)| a + b).collect()
+    }
+
+    pub fn sub(&self, rhs: &Poly) -> Poly {
+        self.add(&rhs.neg())
+    }
+
+    pub fn mul(&self, rhs: &Poly) -> Poly {
+        let mut c = vec![0.0; self.c.len() + rhs.c.len() - 1];
+
+        for (i, a) in self.c.iter().enumerate() {
+            for (j, b) in rhs.c.iter().enumerate() {
+                c[i + j] += a * b;
+            }
+        }
+
+        c.into_iter().collect()
+    }
+
     fn div_once(dividend: &mut Poly, divisor: &Poly) -> Option<f64> {
         if dividend.is_zero() || dividend.degree() < divisor.degree() {
             return None;
@@ -97,15 +142,22 @@ impl Poly {
                 } else {
                     1.0
                 }
-                * if x.is_sign_positive() || self.degree() % 2 == 0 {
+                * if x.is_sign_positive() || self.degree().is_multiple_of(2) {
                     1.0
                 } else {
                     -1.0
                 };
         }
 
-        // mul_add is slower then acc * x + c, but more accurate according to doc
-        self.c.iter().copied().fold(0.0, |acc, c| acc.mul_add(x, c))
+        // mul_add is more accurate, and fast where the target has fma instruction. Without std it
+        // is only available as libm::fma in software, which is about 10x slower, so plain
+        // multiply and add is used instead.
+        #[cfg(feature = "std")]
+        let step = |acc: f64, c| acc.mul_add(x, c);
+        #[cfg(not(feature = "std"))]
+        let step = |acc: f64, c| acc * x + c;
+
+        self.c.iter().copied().fold(0.0, step)
     }
 
     pub fn real_roots(&self) -> Root {
@@ -175,11 +227,14 @@ impl Poly {
     ) -> impl Iterator<Item = f64> {
         assert!(max.is_power_of_two());
 
-        let mut acc = end;
         let mut step = end - start;
+        let mut i = 1.0;
 
+        // multiply instead of accumulate, subtracting a step below float resolution would stay
+        // at the same point forever
         core::iter::from_fn(move || {
-            acc -= step;
+            let mut acc = end - step * i;
+            i += 1.0;
             if acc <= start {
                 step /= 2.0;
                 max /= 2;
@@ -187,6 +242,7 @@ impl Poly {
                     return None;
                 }
                 acc = end - step;
+                i = 2.0;
             }
 
             Some(acc)
@@ -200,17 +256,22 @@ impl Poly {
             .chain(Self::interval_binary_split_points(
                 start,
                 end,
-                1_usize.rotate_right(1),
+                MAX_NEWTON_START,
             ))
             .filter_map(|at| self.newton_find_root_at(d, at))
             .find(|x| start < *x && *x <= end)
+            .or_else(|| {
+                // interval can not be split by floating point, root is already located
+                let mid = (start + end) / 2.0;
+                (mid <= start || mid >= end).then_some(end)
+            })
     }
 
     fn newton_find_root_at(&self, d: &Poly, at: f64) -> Option<f64> {
         let eps = -f64::EPSILON..=f64::EPSILON;
         let mut x = at;
 
-        loop {
+        for _ in 0..MAX_NEWTON_ITERATION {
             let dv = d.eval(x);
             let fv = self.eval(x);
 
@@ -226,10 +287,13 @@ impl Poly {
             let delta = fv / dv;
             x -= delta;
 
-            if eps.contains(&delta) {
+            // relative to x, or it may jump between two adjacent floats forever
+            if libm::fabs(delta) <= NEWTON_EPSILON * f64::max(1.0, libm::fabs(x)) {
                 return Some(x);
             }
         }
+
+        None
     }
 }
 
@@ -286,6 +350,15 @@ mod test {
         assert_eq!(Poly::zero().derivative(), Poly::zero())
     }
 
+    #[test]
+    fn poly_integral() {
+        // 3x^2 + 2x + 1
+        let p: Poly = [3.0, 2.0, 1.0].into_iter().collect();
+        assert_eq!(p.integral().c, [1.0, 1.0, 1.0, 0.0]);
+        assert_eq!(p.integral().derivative(), p);
+        assert_eq!(Poly::zero().integral(), Poly::zero());
+    }
+
     #[test]
     fn poly_div() {
         let dividend: Poly = [1.0, -12.0, 0.0, -42.0].into_iter().collect();
@@ -304,6 +377,16 @@ mod test {
         assert!(r.is_zero());
     }
 
+    #[test]
+    fn poly_add_mul() {
+        let a: Poly = [1.0, 2.0].into_iter().collect();
+        let b: Poly = [1.0, 0.0, -3.0].into_iter().collect();
+        assert_eq!(a.add(&b).c, [1.0, 1.0, -1.0]);
+        assert_eq!(a.sub(&a), Poly::zero());
+        // (x + 2)(x^2 - 3)
+        assert_eq!(a.mul(&b).c, [1.0, 2.0, -3.0, -6.0]);
+    }
+
     #[test]
     fn poly_eval() {
         let poly: Poly = [2.0, -6.0, 2.0, -1.0].into_iter().collect();
@@ -322,4 +405,85 @@ mod test {
         let poly: Poly = [1.0, -2.0, 0.25, 0.75].into_iter().collect();
         dbg!(poly.real_roots());
     }
+
+    #[test]
+    fn poly_real_roots_newton_converge() {
+        // newton iteration jumps between two adjacent floats near root 0.765
+        let poly: Poly = [
+            28665.528035847463,
+            -44480.963314480614,
+            20409.869784690934,
+            -2415.8546025118485,
+        ]
+        .into_iter()
+        .collect();
+
+        let Root::Roots(roots) = poly.real_roots_in(0.0..=1.0) else {
+            panic!()
+        };
+        assert!(roots.iter().all(|r| poly.eval(*r).abs() < 1e-6));
+    }
+
+    #[test]
+    fn poly_real_roots_close() {
+        // sturm sequence counts two roots near 0.175 which bisection can not separate
+        let poly: Poly = [
+            884.7190761623557,
+            -1596.8005492360287,
+            1005.8866380142284,
+            -287.91271416285025,
+            38.4358677097445,
+            -1.94745911986772,
+        ]
+        .into_iter()
+        .collect();
+
+        let Root::Roots(roots) = poly.real_roots_in(0.0..=1.0) else {
+            panic!()
+        };
+        assert_eq!(roots.len(), 4);
+        assert!(roots.iter().any(|r| (r - 0.17497334740962).abs() < 1e-9));
+    }
+
+    #[test]
+    fn poly_real_roots_newton_start() {
+        // no newton start point converges inside the interval after root 0.622, start points
+        // were split below float resolution forever
+        let poly: Poly = [
+            654.7915380882979,
+            -1754.8143653611862,
+            1720.019407442792,
+            -733.2508779659482,
+            114.90713635298556,
+        ]
+        .into_iter()
+        .collect();
+
+        let Root::Roots(roots) = poly.real_roots_in(0.0..=1.0) else {
+            panic!()
+        };
+        assert_eq!(roots.len(), 3);
+        assert!(roots.iter().all(|r| poly.eval(*r).abs() < 1e-6));
+    }
+
+    #[test]
+    fn poly_real_roots_sign_changes_increase() {
+        // floating error makes sign changes of sturm sequence increase across an interval,
+        // which was a subtraction overflow
+        let poly: Poly = [
+            7186.884900427458,
+            1.7562373790497077,
+            0.0,
+            -119998.24376262096,
+            -7186.884900427458,
+        ]
+        .into_iter()
+        .collect();
+
+        let Root::Roots(roots) = poly.real_roots_in(-1.0..=1.0) else {
+            panic!()
+        };
+        assert_eq!(roots.len(), 1);
+        assert!(poly.eval(roots[0]).abs() < 1e-6);
+    }
 }
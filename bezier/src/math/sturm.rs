@@ -1,5 +1,5 @@
 use alloc::vec::Vec;
-use core::ops::{Deref, Range};
+use core::ops::Deref;
 
 use super::Poly;
 
@@ -27,8 +27,8 @@ enum IsolateTask {
 }
 
 enum IsolateTaskResult {
-    Discard(usize, usize),
-    Return(usize, usize),
+    Discard(usize),
+    Return(usize),
     Split(usize, usize),
 }
 
@@ -142,8 +142,8 @@ impl SturmSeq {
                     let result = self.root_range_check(start, end, s, e, state.eps);
 
                     match result {
-                        IsolateTaskResult::Discard(_, _) => (),
-                        IsolateTaskResult::Return(_, _) => return Some((start, end)),
+                        IsolateTaskResult::Discard(_) => (),
+                        IsolateTaskResult::Return(_) => return Some((start, end)),
                         IsolateTaskResult::Split(s, e) => {
                             state.add_task(IsolateTask::Split(start, end, s, e));
                         }
@@ -153,13 +153,19 @@ impl SturmSeq {
                     let mid = (start + end) / 2.0;
                     let roots = s - e;
 
+                    // Roots too close to be separated by floating point, e.g. numerically a
+                    // double root, return the whole interval instead of splitting forever.
+                    if mid <= start || mid >= end {
+                        return Some((start, end));
+                    }
+
                     let left = self.root_range_check(start, mid, Some(s), None, state.eps);
 
                     match left {
-                        IsolateTaskResult::Discard(_, m) => {
+                        IsolateTaskResult::Discard(m) => {
                             state.add_task(IsolateTask::Check(mid, end, Some(m), Some(e)));
                         }
-                        IsolateTaskResult::Return(_, m) => {
+                        IsolateTaskResult::Return(m) => {
                             if roots > 1 {
                                 state.add_task(IsolateTask::Check(mid, end, Some(m), Some(e)));
                             }
@@ -185,12 +191,21 @@ impl SturmSeq {
         let s = s.unwrap_or_else(|| self.sign_changes_at(start));
         let e = e.unwrap_or_else(|| self.sign_changes_at(end));
 
-        if e == s {
-            return IsolateTaskResult::Discard(s, e);
+        // sign changes never increase in exact arithmetic, but the tail of the sequence may be
+        // below floating error with a wrong sign. Then the counts can't be trusted, and only a
+        // sign change of the polynomial itself tells there is a root
+        let s = if e >= s && self[0].eval(start) * self[0].eval(end) < 0.0 {
+            e + 1
+        } else {
+            s
+        };
+
+        if e >= s {
+            return IsolateTaskResult::Discard(e);
         }
 
         if e + 1 == s && start + eps >= end {
-            return IsolateTaskResult::Return(s, e);
+            return IsolateTaskResult::Return(e);
         }
 
         IsolateTaskResult::Split(s, e)
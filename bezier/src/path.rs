@@ -0,0 +1,250 @@
+use alloc::vec::Vec;
+use core::slice;
+
+use crate::{FillRule, Nearest, Point2D, Rect, SecondMoments, Shape};
+
+/// Compound path made of several [Shape] subpaths, such as a glyph "O" with its hole.
+#[derive(Default, Clone)]
+pub struct Path<P> {
+    subpaths: Vec<Shape<P>>,
+}
+
+impl<P> Path<P> {
+    pub fn new() -> Self {
+        Self {
+            subpaths: Vec::new(),
+        }
+    }
+
+    /// Count of subpaths.
+    pub fn len(&self) -> usize {
+        self.subpaths.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.subpaths.is_empty()
+    }
+
+    pub fn subpaths(&self) -> &[Shape<P>] {
+        &self.subpaths
+    }
+
+    pub fn subpaths_mut(&mut self) -> &mut [Shape<P>] {
+        &mut self.subpaths
+    }
+
+    pub fn iter(&self) -> slice::Iter<'_, Shape<P>> {
+        self.subpaths.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, Shape<P>> {
+        self.subpaths.iter_mut()
+    }
+
+    pub fn push(&mut self, shape: Shape<P>) {
+        self.subpaths.push(shape);
+    }
+
+    pub fn insert(&mut self, index: usize, shape: Shape<P>) {
+        self.subpaths.insert(index, shape);
+    }
+
+    pub fn remove(&mut self, index: usize) -> Shape<P> {
+        self.subpaths.remove(index)
+    }
+}
+
+impl<P: Point2D> Path<P> {
+    /// Exact bounding box of all subpaths, `None` if no subpath has any point.
+    pub fn bounds(&self) -> Option<Rect<P>> {
+        self.subpaths
+            .iter()
+            .filter_map(Shape::bounds)
+            .reduce(|a, b| a.union(&b))
+    }
+
+    /// Bounding box of all points and control points of subpaths.
+    pub fn control_bounds(&self) -> Option<Rect<P>> {
+        self.subpaths
+            .iter()
+            .filter_map(Shape::control_bounds)
+            .reduce(|a, b| a.union(&b))
+    }
+
+    /// Nearest endpoint of all subpaths, with the index of subpath it belongs to.
+    pub fn nearest_endpoint(&self, target: &P) -> Option<(usize, Nearest<P>)> {
+        self.subpaths
+            .iter()
+            .enumerate()
+            .filter_map(|(i, s)| s.nearest_endpoint(target).map(|n| (i, n)))
+            .min_by(|a, b| a.1.cmp(&b.1))
+    }
+
+    /// Nearest point on curves of all subpaths, with the index of subpath it belongs to.
+    ///
+    /// `index` of the [Nearest] is the curve index inside that subpath.
+    pub fn nearest_point_on_curves(
+        &self, target: &P, allow_endpoint: bool,
+    ) -> Option<(usize, Nearest<P>)> {
+        self.subpaths
+            .iter()
+            .enumerate()
+            .filter_map(|(i, s)| {
+                s.nearest_point_on_curves(target, allow_endpoint)
+                    .map(|n| (i, n))
+            })
+            .min_by(|a, b| a.1.cmp(&b.1))
+    }
+
+    /// Sum of winding numbers of all subpaths around `target`.
+    pub fn winding(&self, target: &P) -> i32 {
+        self.subpaths.iter().map(|s| s.winding(target)).sum()
+    }
+
+    /// Check if `target` is inside the path under the fill rule, with all subpaths filled
+    /// together.
+    pub fn contains(&self, target: &P, rule: FillRule) -> bool {
+        rule.is_inside(self.winding(target))
+    }
+
+    /// Sum of signed areas of all subpaths, so a hole in the opposite direction is subtracted.
+    pub fn area(&self) -> f64 {
+        self.subpaths.iter().map(Shape::area).sum()
+    }
+
+    /// Centroid of the area of all subpaths, weighted by their signed areas, `None` if the total
+    /// area is zero.
+    pub fn centroid(&self) -> Option<P> {
+        let (mut area, mut x, mut y) = (0.0, 0.0, 0.0);
+        for s in &self.subpaths {
+            let a = s.area();
+            if let Some(c) = s.centroid() {
+                area += a;
+                x += c.x() * a;
+                y += c.y() * a;
+            }
+        }
+
+        (area != 0.0).then(|| P::from_xy(x / area, y / area))
+    }
+
+    /// Sum of second moments of area of all subpaths, about the axes through the origin.
+    pub fn second_moments(&self) -> SecondMoments {
+        self.subpaths.iter().map(Shape::second_moments).fold(
+            SecondMoments {
+                xx: 0.0,
+                yy: 0.0,
+                xy: 0.0,
+            },
+            |a, b| SecondMoments {
+                xx: a.xx + b.xx,
+                yy: a.yy + b.yy,
+                xy: a.xy + b.xy,
+            },
+        )
+    }
+}
+
+impl<P> From<Shape<P>> for Path<P> {
+    fn from(shape: Shape<P>) -> Self {
+        Self {
+            subpaths: vec![shape],
+        }
+    }
+}
+
+impl<P> FromIterator<Shape<P>> for Path<P> {
+    fn from_iter<T: IntoIterator<Item = Shape<P>>>(iter: T) -> Self {
+        Self {
+            subpaths: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<P> IntoIterator for Path<P> {
+    type IntoIter = alloc::vec::IntoIter<Shape<P>>;
+    type Item = Shape<P>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.subpaths.into_iter()
+    }
+}
+
+impl<'a, P> IntoIterator for &'a Path<P> {
+    type IntoIter = slice::Iter<'a, Shape<P>>;
+    type Item = &'a Shape<P>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.subpaths.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ShapePainter;
+
+    fn square(x: f64, y: f64, size: f64) -> Shape<(f64, f64)> {
+        let mut painter = ShapePainter::new();
+        painter
+            .move_to((x, y))
+            .line_to((x + size, y))
+            .line_to((x + size, y + size))
+            .line_to((x, y + size))
+            .close();
+        painter.finish()
+    }
+
+    #[test]
+    fn path_bounds_and_nearest() {
+        let path: Path<_> = [square(0.0, 0.0, 10.0), square(3.0, 3.0, 4.0)]
+            .into_iter()
+            .collect();
+        assert_eq!(path.len(), 2);
+
+        let bounds = path.bounds().unwrap();
+        assert_eq!((bounds.width(), bounds.height()), (10.0, 10.0));
+
+        let (i, nearest) = path.nearest_point_on_curves(&(5.0, 2.0), true).unwrap();
+        assert_eq!(i, 1);
+        assert_eq!(nearest.index, 0);
+        assert!((nearest.distance - 1.0).abs() < 1e-12);
+
+        let (i, nearest) = path.nearest_endpoint(&(-1.0, -1.0)).unwrap();
+        assert_eq!(i, 0);
+        assert_eq!(nearest.point, (0.0, 0.0));
+
+        assert!(Path::<(f64, f64)>::new().bounds().is_none());
+    }
+
+    #[test]
+    fn path_contains_hole() {
+        let path = Path::parse_svg_path("M0 0 H10 V10 H0 Z M3 3 V7 H7 V3 Z").unwrap();
+        assert!(path.contains(&(1.0, 1.0), FillRule::NonZero));
+        assert!(!path.contains(&(5.0, 5.0), FillRule::NonZero));
+        assert!(!path.contains(&(5.0, 5.0), FillRule::EvenOdd));
+
+        // inner square in the same direction only makes a hole under even-odd
+        let path = Path::parse_svg_path("M0 0 H10 V10 H0 Z M3 3 H7 V7 H3 Z").unwrap();
+        assert!(path.contains(&(5.0, 5.0), FillRule::NonZero));
+        assert!(!path.contains(&(5.0, 5.0), FillRule::EvenOdd));
+        assert!(!path.contains(&(12.0, 5.0), FillRule::EvenOdd));
+    }
+
+    #[test]
+    fn path_area_with_hole() {
+        // 10 x 10 square with a 4 x 4 hole in the opposite direction at its corner
+        let path = Path::parse_svg_path("M0 0 H10 V10 H0 Z M0 0 V4 H4 V0 Z").unwrap();
+        assert_eq!(path.area(), 84.0);
+
+        // (100 * 5 - 16 * 2) / 84
+        let (x, y) = path.centroid().unwrap();
+        assert!((x - 468.0 / 84.0).abs() < 1e-12);
+        assert!((y - 468.0 / 84.0).abs() < 1e-12);
+
+        // 10^4 / 3 - 4^4 / 3
+        let moments = path.second_moments();
+        assert!((moments.xx - 3248.0).abs() < 1e-9);
+        assert!((moments.yy - 3248.0).abs() < 1e-9);
+    }
+}
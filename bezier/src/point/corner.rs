@@ -1,4 +1,5 @@
 use super::Point2D;
+use crate::ArcParams;
 
 /// Control point is free, you can use
 /// `{in/out}_ctrl_mut` / `update_{in/out}_ctrl` / `remove_{in/out}_ctrl` to change them as you
@@ -6,11 +7,16 @@ use super::Point2D;
 ///
 /// But center point can only be modified by `move_delta` and `move_to` function, which allows
 /// the control points follow its movement.
+///
+/// Instead of the out ctrl, the curve to next point can be an elliptical arc, set by
+/// `{with/update/remove}_out_arc`. Out ctrl and out arc exclude each other, setting one removes
+/// the other.
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct CornerPoint<P> {
     in_ctrl: Option<P>,
     point: P,
     out_ctrl: Option<P>,
+    out_arc: Option<ArcParams<P>>,
 }
 
 /// Builder
@@ -20,6 +26,7 @@ impl<P> CornerPoint<P> {
             point,
             in_ctrl: None,
             out_ctrl: None,
+            out_arc: None,
         }
     }
 
@@ -29,7 +36,12 @@ impl<P> CornerPoint<P> {
     }
 
     pub fn with_out_ctrl(mut self, point: P) -> Self {
-        self.out_ctrl.replace(point);
+        self.update_out_ctrl(point);
+        self
+    }
+
+    pub fn with_out_arc(mut self, arc: ArcParams<P>) -> Self {
+        self.update_out_arc(arc);
         self
     }
 }
@@ -73,12 +85,34 @@ impl<P> CornerPoint<P> {
     }
 
     pub fn update_out_ctrl(&mut self, val: P) {
+        self.out_arc.take();
         self.out_ctrl.replace(val);
     }
 
     pub fn remove_out_ctrl(&mut self) {
         self.out_ctrl.take();
     }
+
+    pub fn has_out_arc(&self) -> bool {
+        self.out_arc.is_some()
+    }
+
+    pub fn out_arc(&self) -> Option<&ArcParams<P>> {
+        self.out_arc.as_ref()
+    }
+
+    pub fn out_arc_mut(&mut self) -> Option<&mut ArcParams<P>> {
+        self.out_arc.as_mut()
+    }
+
+    pub fn update_out_arc(&mut self, val: ArcParams<P>) {
+        self.out_ctrl.take();
+        self.out_arc.replace(val);
+    }
+
+    pub fn remove_out_arc(&mut self) {
+        self.out_arc.take();
+    }
 }
 
 /// Move
@@ -5,10 +5,14 @@ use alloc::borrow::Cow;
 use core::cmp::Ordering;
 
 pub use self::{corner::*, smooth::*};
+use crate::ArcParams;
 
 pub type TuplePoint2D = (f64, f64);
 pub type ArrayPoint2D = [f64; 2];
 
+// Max sine of the angle between two ctrl handles which still be treated as collinear.
+const SMOOTH_TOLERANCE: f64 = 1e-6;
+
 pub trait Point2D: Clone {
     fn x(&self) -> f64;
     fn y(&self) -> f64;
@@ -101,6 +105,13 @@ pub trait Point2D: Clone {
         x1 * x2 + y1 * y2
     }
 
+    #[inline(always)]
+    fn cross(&self, rhs: &Self) -> f64 {
+        let [x1, y1] = self.array();
+        let [x2, y2] = rhs.array();
+        x1 * y2 - y1 * x2
+    }
+
     #[inline(always)]
     fn scale(&self, rhs: f64) -> Self {
         let [x, y] = self.array();
@@ -180,6 +191,44 @@ impl<P> CurvePoint<P> {
 }
 
 impl<P: Point2D> CurvePoint<P> {
+    /// Create a point from its position and optional control points.
+    ///
+    /// If both control points exist and are collinear with the point (on opposite sides of it), a
+    /// [SmoothPoint] is created, otherwise a [CornerPoint].
+    pub fn from_ctrls(point: P, in_ctrl: Option<P>, out_ctrl: Option<P>) -> Self {
+        match (in_ctrl, out_ctrl) {
+            (Some(in_ctrl), Some(out_ctrl)) if Self::is_smooth(&point, &in_ctrl, &out_ctrl) => {
+                let mut p = SmoothPoint::horizontal(point, 1.0, 1.0);
+                p.move_in_ctrl_to(&in_ctrl);
+                p.move_out_ctrl_to(&out_ctrl);
+                p.into()
+            }
+            (in_ctrl, out_ctrl) => {
+                let mut p = CornerPoint::new(point);
+                if let Some(c) = in_ctrl {
+                    p = p.with_in_ctrl(c);
+                }
+                if let Some(c) = out_ctrl {
+                    p = p.with_out_ctrl(c);
+                }
+                p.into()
+            }
+        }
+    }
+
+    fn is_smooth(point: &P, in_ctrl: &P, out_ctrl: &P) -> bool {
+        let a = point.minus(in_ctrl);
+        let b = out_ctrl.minus(point);
+
+        let la = a.length_from_origin();
+        let lb = b.length_from_origin();
+        if la == 0.0 || lb == 0.0 {
+            return false;
+        }
+
+        a.dot(&b) > 0.0 && libm::fabs(a.cross(&b)) <= SMOOTH_TOLERANCE * la * lb
+    }
+
     pub fn in_ctrl(&self) -> Option<Cow<'_, P>> {
         match self {
             Self::Corner(c) => c.in_ctrl().map(Cow::Borrowed),
@@ -207,6 +256,29 @@ impl<P: Point2D> CurvePoint<P> {
             Self::Smooth(s) => s.move_out_ctrl_to(&val),
         };
     }
+
+    /// Elliptical arc to the next point, which takes the place of out ctrl. Only [CornerPoint]
+    /// can have it.
+    pub fn out_arc(&self) -> Option<&ArcParams<P>> {
+        match self {
+            Self::Corner(c) => c.out_arc(),
+            Self::Smooth(_) => None,
+        }
+    }
+
+    /// Set the arc to the next point and remove out ctrl, [SmoothPoint] becomes [CornerPoint]
+    /// with its in ctrl kept.
+    pub fn update_out_arc(&mut self, val: ArcParams<P>) {
+        match self {
+            Self::Corner(c) => c.update_out_arc(val),
+            Self::Smooth(s) => {
+                *self = CornerPoint::new(s.point().clone())
+                    .with_in_ctrl(s.in_ctrl())
+                    .with_out_arc(val)
+                    .into();
+            }
+        };
+    }
 }
 
 impl<P> From<CornerPoint<P>> for CurvePoint<P> {
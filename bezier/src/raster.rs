@@ -0,0 +1,344 @@
+use alloc::vec::Vec;
+
+use crate::{FillRule, Path, Point2D, Shape, Transform};
+
+// Max deviation in pixel when flattening curves.
+const FLATTEN_TOLERANCE: f64 = 0.05;
+
+// Lines narrower than this in a row are treated as vertical, avoiding division by zero width.
+const VERTICAL_EPSILON: f64 = 1e-12;
+
+/// 8-bit coverage of each pixel, 0 is empty and 255 is fully covered, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mask {
+    width: usize,
+    height: usize,
+    data: Vec<u8>,
+}
+
+impl Mask {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Coverage of all pixels, row by row from top, `width * height` in total.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> u8 {
+        assert!(x < self.width && y < self.height);
+        self.data[y * self.width + x]
+    }
+}
+
+// Integral of clamp(s, 0, 1) from -inf to s.
+fn ramp_integral(s: f64) -> f64 {
+    if s <= 0.0 {
+        0.0
+    } else if s < 1.0 {
+        s * s / 2.0
+    } else {
+        s - 0.5
+    }
+}
+
+/// Scanline rasterizer with analytic anti-aliasing.
+///
+/// Pixel (x, y) covers the square from (x, y) to (x + 1, y + 1) in device space. Each line
+/// adds its signed area to the pixels of rows it crosses, accumulated from left to right, so
+/// the coverage is exact for any single edge in a pixel.
+///
+/// Fill rules are applied to the accumulated winding, which is fractional where several edges
+/// share a pixel, so such pixels are approximated.
+#[derive(Debug, Clone)]
+pub struct Rasterizer {
+    width: usize,
+    height: usize,
+    // coverage change of each cell from the cell to its left, two more cells each row for
+    // lines touching the right border
+    cells: Vec<f64>,
+}
+
+impl Rasterizer {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![0.0; (width + 2) * height],
+        }
+    }
+
+    fn stride(&self) -> usize {
+        self.width + 2
+    }
+
+    /// Add a line in device space, positive winding if it goes to y+.
+    pub fn add_line<P: Point2D>(&mut self, from: &P, to: &P) {
+        let (x0, y0, x1, y1) = (from.x(), from.y(), to.x(), to.y());
+        let w = self.width as f64;
+
+        if y0 == y1 || !(x0.is_finite() && y0.is_finite() && x1.is_finite() && y1.is_finite()) {
+            return;
+        }
+
+        // part right of the buffer never covers a pixel, part left of it covers the whole row, as
+        // a vertical line at x = 0
+        let clip = |x: f64| x.clamp(0.0, w);
+        let mut ts = [0.0; 2];
+        let mut len = 0;
+        for x in [0.0, w] {
+            if (x0 < x) != (x1 < x) {
+                ts[len] = (x - x0) / (x1 - x0);
+                len += 1;
+            }
+        }
+        if len == 2 && ts[0] > ts[1] {
+            ts.swap(0, 1);
+        }
+
+        let mut t0 = 0.0;
+        for t in ts[..len].iter().copied().chain(Some(1.0)) {
+            let (xa, ya) = (x0 + (x1 - x0) * t0, y0 + (y1 - y0) * t0);
+            let (xb, yb) = (x0 + (x1 - x0) * t, y0 + (y1 - y0) * t);
+
+            if (xa + xb) / 2.0 < w {
+                self.clipped_line(clip(xa), ya, clip(xb), yb);
+            }
+            t0 = t;
+        }
+    }
+
+    // Line inside [0, width] horizontally.
+    fn clipped_line(&mut self, x0: f64, y0: f64, x1: f64, y1: f64) {
+        let (dir, (x0, y0), (x1, y1)) = if y0 < y1 {
+            (1.0, (x0, y0), (x1, y1))
+        } else {
+            (-1.0, (x1, y1), (x0, y0))
+        };
+        let dxdy = (x1 - x0) / (y1 - y0);
+
+        let top = libm::floor(y0).max(0.0) as usize;
+        let bottom = (libm::ceil(y1).max(0.0) as usize).min(self.height);
+
+        for row in top..bottom {
+            let ya = y0.max(row as f64);
+            let yb = y1.min((row + 1) as f64);
+            if ya >= yb {
+                continue;
+            }
+
+            let xa = x0 + (ya - y0) * dxdy;
+            let xb = x0 + (yb - y0) * dxdy;
+            self.row_span(row, xa.min(xb), xa.max(xb), dir * (yb - ya));
+        }
+    }
+
+    // A piece of line inside one row, whose x goes across [left, right] and height is `height`.
+    fn row_span(&mut self, row: usize, left: f64, right: f64, height: f64) {
+        let width = right - left;
+
+        // coverage of the cell `i` by the part of row right of the line
+        let coverage = |i: usize| {
+            let edge = (i + 1) as f64;
+            let c = if width < VERTICAL_EPSILON {
+                (edge - (left + right) / 2.0).clamp(0.0, 1.0)
+            } else {
+                (ramp_integral(edge - left) - ramp_integral(edge - right)) / width
+            };
+            c * height
+        };
+
+        let stride = self.stride();
+        let cells = &mut self.cells[row * stride..(row + 1) * stride];
+
+        let first = libm::floor(left) as usize;
+        let last = (libm::ceil(right) as usize).min(stride - 1);
+
+        let mut prev = 0.0;
+        for (i, cell) in cells.iter_mut().enumerate().take(last + 1).skip(first) {
+            let c = coverage(i);
+            *cell += c - prev;
+            prev = c;
+        }
+    }
+
+    /// Add the outline of a shape, mapped to device space by the transform.
+    ///
+    /// An open shape is closed by a line segment as how it's filled.
+    pub fn add_shape<P: Point2D>(&mut self, shape: &Shape<P>, transform: &Transform) {
+        let tolerance = FLATTEN_TOLERANCE / transform.max_scale().max(f64::MIN_POSITIVE);
+
+        let mut points: Vec<P> = shape.flatten(tolerance);
+        if let Some(first) = points.first() {
+            points.push(first.clone());
+        }
+
+        let points: Vec<P> = points.iter().map(|p| transform.apply(p)).collect();
+        for w in points.windows(2) {
+            self.add_line(&w[0], &w[1]);
+        }
+    }
+
+    /// Add all subpaths of a path, see [Rasterizer::add_shape].
+    pub fn add_path<P: Point2D>(&mut self, path: &Path<P>, transform: &Transform) {
+        for shape in path {
+            self.add_shape(shape, transform);
+        }
+    }
+
+    /// Accumulate coverage of each pixel under the fill rule.
+    pub fn finish(self, rule: FillRule) -> Mask {
+        let mut data = Vec::with_capacity(self.width * self.height);
+
+        for row in self.cells.chunks_exact(self.stride()) {
+            let mut winding = 0.0;
+            for cell in &row[..self.width] {
+                winding += cell;
+
+                let coverage = match rule {
+                    FillRule::NonZero => libm::fabs(winding).min(1.0),
+                    FillRule::EvenOdd => {
+                        let w = libm::fmod(libm::fabs(winding), 2.0);
+                        if w > 1.0 {
+                            2.0 - w
+                        } else {
+                            w
+                        }
+                    }
+                };
+                data.push(libm::round(coverage * 255.0) as u8);
+            }
+        }
+
+        Mask {
+            width: self.width,
+            height: self.height,
+            data,
+        }
+    }
+}
+
+impl<P: Point2D> Shape<P> {
+    /// Rasterize the shape into a `width` x `height` coverage mask, with anti-aliasing.
+    ///
+    /// The transform maps the shape into device space, see [Rasterizer].
+    pub fn rasterize(
+        &self, width: usize, height: usize, transform: &Transform, rule: FillRule,
+    ) -> Mask {
+        let mut rasterizer = Rasterizer::new(width, height);
+        rasterizer.add_shape(self, transform);
+        rasterizer.finish(rule)
+    }
+}
+
+impl<P: Point2D> Path<P> {
+    /// Rasterize all subpaths into a `width` x `height` coverage mask, with anti-aliasing.
+    ///
+    /// The transform maps the path into device space, see [Rasterizer].
+    pub fn rasterize(
+        &self, width: usize, height: usize, transform: &Transform, rule: FillRule,
+    ) -> Mask {
+        let mut rasterizer = Rasterizer::new(width, height);
+        rasterizer.add_path(self, transform);
+        rasterizer.finish(rule)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::f64::consts::PI;
+
+    use super::*;
+    use crate::ShapePainter;
+
+    fn total(mask: &Mask) -> f64 {
+        mask.data().iter().map(|c| *c as f64 / 255.0).sum()
+    }
+
+    #[test]
+    fn raster_square() {
+        let shape = Shape::<(f64, f64)>::parse_svg_path("M1 1 H3 V3 H1 Z").unwrap();
+
+        let mask = shape.rasterize(4, 4, &Transform::identity(), FillRule::NonZero);
+        #[rustfmt::skip]
+        assert_eq!(mask.data(), [
+            0, 0, 0, 0,
+            0, 255, 255, 0,
+            0, 255, 255, 0,
+            0, 0, 0, 0,
+        ]);
+
+        // half pixel offset, edges cover half of pixels and corners a quarter
+        let t = Transform::translate(0.5, 0.5);
+        let mask = shape.rasterize(4, 4, &t, FillRule::NonZero);
+        assert_eq!(mask.get(1, 1), 64);
+        assert_eq!(mask.get(2, 1), 128);
+        assert_eq!(mask.get(2, 2), 255);
+        assert_eq!(mask.get(3, 3), 64);
+        assert_eq!(mask.get(0, 0), 0);
+
+        // clipped by borders, only [0, 1] x [3, 4] is visible
+        let t = Transform::translate(-2.0, 2.0);
+        let mask = shape.rasterize(4, 4, &t, FillRule::NonZero);
+        assert_eq!(mask.get(0, 3), 255);
+        assert_eq!(total(&mask), 1.0);
+        let mask = shape.rasterize(4, 4, &Transform::translate(2.0, 0.0), FillRule::NonZero);
+        assert_eq!(mask.get(3, 1), 255);
+        assert_eq!(total(&mask), 2.0);
+    }
+
+    #[test]
+    fn raster_fill_rules() {
+        // inner square in the same direction
+        let path = Path::<(f64, f64)>::parse_svg_path("M0 0 H6 V6 H0 Z M2 2 H4 V4 H2 Z").unwrap();
+
+        let mask = path.rasterize(6, 6, &Transform::identity(), FillRule::NonZero);
+        assert_eq!(total(&mask), 36.0);
+
+        let mask = path.rasterize(6, 6, &Transform::identity(), FillRule::EvenOdd);
+        assert_eq!(total(&mask), 32.0);
+        assert_eq!(mask.get(2, 2), 0);
+        assert_eq!(mask.get(1, 2), 255);
+    }
+
+    #[test]
+    fn raster_anti_aliasing() {
+        // unit circle scaled to radius 10 at the center of a 32 x 32 buffer
+        let k = 0.551_915_024_494;
+        let mut painter = ShapePainter::new();
+        painter
+            .move_to((1.0, 0.0))
+            .cubic_to((1.0, k), (k, 1.0), (0.0, 1.0))
+            .cubic_to((-k, 1.0), (-1.0, k), (-1.0, 0.0))
+            .cubic_to((-1.0, -k), (-k, -1.0), (0.0, -1.0))
+            .cubic_to((k, -1.0), (1.0, -k), (1.0, 0.0))
+            .close();
+        let circle = painter.finish();
+
+        let t = Transform::scale(10.0, 10.0).then(&Transform::translate(16.0, 16.0));
+        let mask = circle.rasterize(32, 32, &t, FillRule::NonZero);
+
+        // total coverage is the area, up to rounding to 8 bits on edge pixels
+        assert!((total(&mask) - 100.0 * PI).abs() < 0.5);
+        assert_eq!(mask.get(16, 16), 255);
+        assert_eq!(mask.get(0, 0), 0);
+        // partially covered pixels on the edge
+        let edge = mask
+            .data()
+            .iter()
+            .filter(|c| **c != 0 && **c != 255)
+            .count();
+        assert!(edge > 40);
+
+        // opposite direction gives the same coverage
+        let t = Transform::scale(-10.0, 10.0).then(&Transform::translate(16.0, 16.0));
+        let mirrored = circle.rasterize(32, 32, &t, FillRule::NonZero);
+        assert!((total(&mirrored) - total(&mask)).abs() < 0.5);
+        assert_eq!(mirrored.get(16, 16), 255);
+    }
+}
@@ -0,0 +1,97 @@
+use crate::Point2D;
+
+/// Axis-aligned rectangle, used as bounding box of curves and shapes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rect<P> {
+    min: P,
+    max: P,
+}
+
+impl<P: Point2D> Rect<P> {
+    /// Create a rect from two diagonal corners, in any order.
+    pub fn new(a: P, b: P) -> Self {
+        Self {
+            min: P::from_xy(f64::min(a.x(), b.x()), f64::min(a.y(), b.y())),
+            max: P::from_xy(f64::max(a.x(), b.x()), f64::max(a.y(), b.y())),
+        }
+    }
+
+    /// Create a zero sized rect at point.
+    pub fn from_point(p: P) -> Self {
+        Self {
+            min: p.clone(),
+            max: p,
+        }
+    }
+
+    /// Smallest rect contains all points, `None` if iterator is empty.
+    pub fn from_points<'a, I>(points: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = &'a P>,
+        P: 'a,
+    {
+        let mut iter = points.into_iter();
+        let mut rect = Self::from_point(iter.next()?.clone());
+        for p in iter {
+            rect.include(p);
+        }
+        Some(rect)
+    }
+
+    pub fn min(&self) -> &P {
+        &self.min
+    }
+
+    pub fn max(&self) -> &P {
+        &self.max
+    }
+
+    pub fn width(&self) -> f64 {
+        self.max.x() - self.min.x()
+    }
+
+    pub fn height(&self) -> f64 {
+        self.max.y() - self.min.y()
+    }
+
+    pub fn center(&self) -> P {
+        self.min.plus(&self.max).scale(0.5)
+    }
+
+    /// Extend the rect to contain point.
+    pub fn include(&mut self, p: &P) {
+        self.min = P::from_xy(f64::min(self.min.x(), p.x()), f64::min(self.min.y(), p.y()));
+        self.max = P::from_xy(f64::max(self.max.x(), p.x()), f64::max(self.max.y(), p.y()));
+    }
+
+    /// Smallest rect contains both rect.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut rect = self.clone();
+        rect.include(&other.min);
+        rect.include(&other.max);
+        rect
+    }
+
+    /// Check if point is inside the rect, boundary included.
+    pub fn contains(&self, p: &P) -> bool {
+        self.min.x() <= p.x()
+            && p.x() <= self.max.x()
+            && self.min.y() <= p.y()
+            && p.y() <= self.max.y()
+    }
+
+    /// Check if two rect overlaps, touching boundary is treated as overlapping.
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.min.x() <= other.max.x()
+            && other.min.x() <= self.max.x()
+            && self.min.y() <= other.max.y()
+            && other.min.y() <= self.max.y()
+    }
+
+    /// Distance from point to the rect, zero if point is inside.
+    pub fn distance_to(&self, p: &P) -> f64 {
+        let dx = f64::max(0.0, f64::max(self.min.x() - p.x(), p.x() - self.max.x()));
+        let dy = f64::max(0.0, f64::max(self.min.y() - p.y(), p.y() - self.max.y()));
+        libm::sqrt(dx * dx + dy * dy)
+    }
+}
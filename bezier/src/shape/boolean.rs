@@ -0,0 +1,530 @@
+use alloc::vec::Vec;
+use core::f64::consts::PI;
+
+use super::{
+    dash::sub_curve,
+    stroke::{end_of, is_degenerated, reversed, start_of},
+    ShapePainter,
+};
+use crate::{Bezier, Curve, FillRule, Path, Point2D, QuadBezier, Segment, Shape};
+
+// Intersection tolerance, relative to the size of both operands.
+const INTERSECT_TOLERANCE: f64 = 1e-9;
+
+// Max gap in a contour broken by floating error, relative to the size of both operands, across
+// which it's still linked to the nearest fragment.
+const SNAP_TOLERANCE: f64 = 1e-6;
+
+// Distance of the two probe points from a fragment, relative to the size of both operands.
+const PROBE_DISTANCE: f64 = 1e-7;
+
+// Max difference from π in radians, for a turn angle to be a U-turn.
+const U_TURN_EPSILON: f64 = 1e-9;
+
+// Intersections closer than this in parameter space are merged, and the ones this close to an
+// endpoint are snapped to it.
+const T_EPSILON: f64 = 1e-9;
+
+/// Boolean operation on the areas of two paths, see [Path::boolean].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BooleanOp {
+    /// Area inside either of them.
+    Union,
+    /// Area inside both of them.
+    Intersection,
+    /// Area inside the first one but not the second one.
+    Difference,
+    /// Area inside exactly one of them.
+    Xor,
+}
+
+impl BooleanOp {
+    fn apply(&self, a: bool, b: bool) -> bool {
+        match self {
+            Self::Union => a || b,
+            Self::Intersection => a && b,
+            Self::Difference => a && !b,
+            Self::Xor => a != b,
+        }
+    }
+}
+
+// The same curve with its endpoints moved to the exact ones, which are at most floating error
+// away from the current ones.
+//
+// Arc is kept as is, its endpoints can't be moved without moving its center, and the error is far
+// below the tolerance of linking fragments.
+fn with_endpoints<P: Point2D>(curve: &Curve<P>, start: P, end: P) -> Curve<P> {
+    match curve {
+        Curve::Segment(_) => Curve::Segment(Segment::new(start, end)),
+        Curve::Quad(q) => Curve::Quad(QuadBezier::new(start, q.ctrl.clone(), end)),
+        Curve::Bezier(b) => {
+            Curve::Bezier(Bezier::new(start, b.ctrl1.clone(), b.ctrl2.clone(), end))
+        }
+        Curve::Arc(a) => Curve::Arc(a.clone()),
+    }
+}
+
+// All curves of a path, open subpaths are closed by a line segment as how they are filled.
+fn edges<P: Point2D>(path: &Path<P>) -> Vec<Curve<P>> {
+    let mut edges = Vec::new();
+
+    for shape in path {
+        edges.extend(shape.curves());
+
+        let points = shape.points();
+        if !shape.closed() && points.len() >= 2 {
+            let first = points[0].point().clone();
+            let last = points[points.len() - 1].point().clone();
+            edges.push(Curve::Segment(Segment::new(last, first)));
+        }
+    }
+
+    edges.retain(|c| !is_degenerated(c));
+
+    edges
+}
+
+// Split every edge at its intersections with all other edges, so no fragment crosses another one.
+//
+// Both edges of an intersection are split at the same point, which makes fragments meeting there
+// share their endpoints exactly.
+fn split<P: Point2D>(edges: &[Curve<P>], tolerance: f64) -> Vec<Curve<P>> {
+    let mut splits: Vec<Vec<(f64, P)>> = vec![Vec::new(); edges.len()];
+    let bounds: Vec<_> = edges.iter().map(Curve::control_bounds).collect();
+
+    for i in 0..edges.len() {
+        for j in i + 1..edges.len() {
+            if !bounds[i].intersects(&bounds[j]) {
+                continue;
+            }
+
+            for hit in edges[i].intersections(&edges[j], tolerance) {
+                // prefer existing endpoints, so vertices are kept exactly, and no sliver is split
+                // off next to them
+                let snap = |t: f64, edge: &Curve<P>| {
+                    [(0.0, start_of(edge)), (1.0, end_of(edge))]
+                        .into_iter()
+                        .find(|(end, p)| {
+                            libm::fabs(t - end) <= T_EPSILON || p.distance(&hit.point) <= tolerance
+                        })
+                };
+                let snap_self = snap(hit.t_self, &edges[i]);
+                let snap_other = snap(hit.t_other, &edges[j]);
+
+                let point = match (&snap_self, &snap_other) {
+                    (Some((_, p)), _) | (None, Some((_, p))) => p.clone(),
+                    (None, None) => hit.point.clone(),
+                };
+                let t_self = snap_self.map_or(hit.t_self, |(t, _)| t);
+                let t_other = snap_other.map_or(hit.t_other, |(t, _)| t);
+
+                splits[i].push((t_self, point.clone()));
+                splits[j].push((t_other, point));
+            }
+        }
+    }
+
+    let mut fragments = Vec::new();
+    for (edge, mut ts) in edges.iter().zip(splits) {
+        ts.retain(|(t, _)| T_EPSILON < *t && *t < 1.0 - T_EPSILON);
+        ts.sort_by(|a, b| a.0.total_cmp(&b.0));
+        ts.dedup_by(|b, a| b.0 - a.0 <= T_EPSILON);
+
+        let mut t0 = 0.0;
+        let mut p0 = start_of(edge);
+        for (t, p) in ts.into_iter().chain(Some((1.0, end_of(edge)))) {
+            let fragment = with_endpoints(&sub_curve(edge, t0, t), p0, p.clone());
+            let bounds = fragment.control_bounds();
+            if !is_degenerated(&fragment) && f64::max(bounds.width(), bounds.height()) > tolerance {
+                fragments.push(fragment);
+            }
+            (t0, p0) = (t, p);
+        }
+    }
+
+    fragments
+}
+
+// Check if two fragments are the same one, which happens where edges of two operands overlap.
+fn is_same<P: Point2D>(a: &Curve<P>, b: &Curve<P>, tolerance: f64) -> bool {
+    [0.0, 0.5, 1.0]
+        .into_iter()
+        .all(|t| a.at(t).distance(&b.at(t)) <= tolerance)
+}
+
+// Signed angle turning from the end of `from` to the start of `to`, positive is to the left.
+//
+// A U-turn, whose sign is up to floating error, is taken as the rightmost turn, so contours
+// touching tangentially are kept apart the same as the ones touching at a corner.
+fn turn_angle<P: Point2D>(from: &Curve<P>, to: &Curve<P>) -> f64 {
+    let a = from.tangent(1.0);
+    let b = to.tangent(0.0);
+
+    let angle = libm::atan2(a.cross(&b), a.dot(&b));
+    if libm::fabs(angle) >= PI - U_TURN_EPSILON {
+        -PI
+    } else {
+        angle
+    }
+}
+
+// Link fragments into closed contours, each fragment is used once.
+//
+// Where several fragments start at the end of current one, the one turning most to the left is
+// taken, so contours which touch at a point are kept apart. If none starts there, the contour is
+// broken by floating error, it goes on with the nearest fragment start within `snap`, or closes if
+// its own start is nearer. A contour which still can't be closed doesn't bound an area, it's
+// dropped, so are the ones with less than two points or thinner than `tolerance`.
+fn link<P: Point2D>(fragments: Vec<Curve<P>>, tolerance: f64, snap: f64) -> Vec<Shape<P>> {
+    let mut used = vec![false; fragments.len()];
+    let mut shapes = Vec::new();
+
+    for first in 0..fragments.len() {
+        if used[first] {
+            continue;
+        }
+        used[first] = true;
+
+        let start = start_of(&fragments[first]);
+        let mut chain = vec![first];
+        let closed = loop {
+            let current = chain[chain.len() - 1];
+            let end = end_of(&fragments[current]);
+
+            let next = (0..fragments.len())
+                .filter(|i| !used[*i])
+                .filter(|i| start_of(&fragments[*i]).distance(&end) <= tolerance)
+                .map(|i| (i, turn_angle(&fragments[current], &fragments[i])))
+                .max_by(|a, b| a.1.total_cmp(&b.1));
+
+            // back to the start, unless a fragment turns more to the left there
+            if end.distance(&start) <= tolerance
+                && next.is_none_or(|(_, angle)| {
+                    angle <= turn_angle(&fragments[current], &fragments[first])
+                })
+            {
+                break true;
+            }
+
+            let next = next.map(|(i, _)| i).or_else(|| {
+                (0..fragments.len())
+                    .filter(|i| !used[*i])
+                    .map(|i| (i, start_of(&fragments[i]).distance(&end)))
+                    .filter(|(_, d)| *d <= snap && *d < end.distance(&start))
+                    .min_by(|a, b| a.1.total_cmp(&b.1))
+                    .map(|(i, _)| i)
+            });
+
+            match next {
+                Some(i) => {
+                    used[i] = true;
+                    chain.push(i);
+                }
+                None => break end.distance(&start) <= snap,
+            }
+        };
+
+        if !closed {
+            continue;
+        }
+
+        // each fragment ends exactly where the next one starts, so no gap is filled by a line
+        let mut painter = ShapePainter::new();
+        painter.move_to(start.clone());
+        for (k, &i) in chain.iter().enumerate() {
+            let end = chain
+                .get(k + 1)
+                .map_or(start.clone(), |&j| start_of(&fragments[j]));
+            let fragment = &fragments[i];
+            painter.curve_to(&with_endpoints(fragment, start_of(fragment), end));
+        }
+        painter.close();
+        let shape = painter.finish();
+
+        let thin = shape
+            .control_bounds()
+            .is_none_or(|b| libm::fabs(shape.area()) <= tolerance * (b.width() + b.height()));
+        if shape.len() >= 2 && !thin {
+            shapes.push(shape);
+        }
+    }
+
+    shapes
+}
+
+impl<P: Point2D> Path<P> {
+    /// Boolean operation on the areas of two paths, the inside of each path is decided by the
+    /// fill rule.
+    ///
+    /// Curves in the result are parts of the input curves, split where they intersect, so the
+    /// exact geometry is kept. Each contour of the result has the filled area on its left, which
+    /// is counterclockwise for outer contours and clockwise for holes in a y-up coordinate
+    /// system, so the result is filled correctly under either fill rule. Open subpaths are
+    /// treated as closed by a line segment.
+    pub fn boolean(&self, other: &Self, op: BooleanOp, rule: FillRule) -> Self {
+        let Some(bounds) = [self.control_bounds(), other.control_bounds()]
+            .into_iter()
+            .flatten()
+            .reduce(|a, b| a.union(&b))
+        else {
+            return Self::new();
+        };
+        let size = f64::max(f64::max(bounds.width(), bounds.height()), f64::MIN_POSITIVE);
+        let tolerance = size * INTERSECT_TOLERANCE;
+        let snap = size * SNAP_TOLERANCE;
+        let probe = size * PROBE_DISTANCE;
+
+        let edges: Vec<_> = edges(self).into_iter().chain(edges(other)).collect();
+
+        let inside = |p: &P| {
+            op.apply(
+                rule.is_inside(self.winding(p)),
+                rule.is_inside(other.winding(p)),
+            )
+        };
+
+        // keep fragments which separate inside of the result from outside, with inside on left
+        let mut kept: Vec<Curve<P>> = Vec::new();
+        for fragment in split(&edges, tolerance) {
+            let mid = fragment.at(0.5);
+            let offset = fragment.normal(0.5).scale(probe);
+
+            let fragment = match (inside(&mid.plus(&offset)), inside(&mid.minus(&offset))) {
+                (true, false) => fragment,
+                (false, true) => reversed(&fragment),
+                _ => continue,
+            };
+
+            // overlapped edges of two operands
+            if kept.iter().any(|k| is_same(k, &fragment, tolerance)) {
+                continue;
+            }
+
+            kept.push(fragment);
+        }
+
+        link(kept, tolerance, snap).into_iter().collect()
+    }
+
+    /// Area inside either path, with nonzero fill rule, see [Path::boolean].
+    pub fn union(&self, other: &Self) -> Self {
+        self.boolean(other, BooleanOp::Union, FillRule::NonZero)
+    }
+
+    /// Area inside both paths, with nonzero fill rule, see [Path::boolean].
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.boolean(other, BooleanOp::Intersection, FillRule::NonZero)
+    }
+
+    /// Area inside this path but not the other one, with nonzero fill rule, see [Path::boolean].
+    pub fn difference(&self, other: &Self) -> Self {
+        self.boolean(other, BooleanOp::Difference, FillRule::NonZero)
+    }
+
+    /// Area inside exactly one of the paths, with nonzero fill rule, see [Path::boolean].
+    pub fn xor(&self, other: &Self) -> Self {
+        self.boolean(other, BooleanOp::Xor, FillRule::NonZero)
+    }
+}
+
+impl<P: Point2D> Shape<P> {
+    /// Boolean operation on the areas of two shapes, the result may have several contours.
+    ///
+    /// See [Path::boolean] for details.
+    pub fn boolean(&self, other: &Self, op: BooleanOp, rule: FillRule) -> Path<P> {
+        Path::from(self.clone()).boolean(&Path::from(other.clone()), op, rule)
+    }
+
+    /// Area inside either shape, with nonzero fill rule.
+    pub fn union(&self, other: &Self) -> Path<P> {
+        self.boolean(other, BooleanOp::Union, FillRule::NonZero)
+    }
+
+    /// Area inside both shapes, with nonzero fill rule.
+    pub fn intersection(&self, other: &Self) -> Path<P> {
+        self.boolean(other, BooleanOp::Intersection, FillRule::NonZero)
+    }
+
+    /// Area inside this shape but not the other one, with nonzero fill rule.
+    pub fn difference(&self, other: &Self) -> Path<P> {
+        self.boolean(other, BooleanOp::Difference, FillRule::NonZero)
+    }
+
+    /// Area inside exactly one of the shapes, with nonzero fill rule.
+    pub fn xor(&self, other: &Self) -> Path<P> {
+        self.boolean(other, BooleanOp::Xor, FillRule::NonZero)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::CurvePoint;
+
+    fn square(x: f64, y: f64, size: f64) -> Shape<(f64, f64)> {
+        let mut painter = ShapePainter::new();
+        painter
+            .move_to((x, y))
+            .line_to((x + size, y))
+            .line_to((x + size, y + size))
+            .line_to((x, y + size))
+            .close();
+        painter.finish()
+    }
+
+    // circle approximated by four cubic curves
+    fn circle(cx: f64, cy: f64, r: f64) -> Shape<(f64, f64)> {
+        let k = 0.551_915_024_494 * r;
+        let mut painter = ShapePainter::new();
+        painter
+            .move_to((cx + r, cy))
+            .cubic_to((cx + r, cy + k), (cx + k, cy + r), (cx, cy + r))
+            .cubic_to((cx - k, cy + r), (cx - r, cy + k), (cx - r, cy))
+            .cubic_to((cx - r, cy - k), (cx - k, cy - r), (cx, cy - r))
+            .cubic_to((cx + k, cy - r), (cx + r, cy - k), (cx + r, cy))
+            .close();
+        painter.finish()
+    }
+
+    // exact circle made of four arcs
+    fn arc_circle(cx: f64, cy: f64, r: f64) -> Shape<(f64, f64)> {
+        let mut painter = ShapePainter::new();
+        painter.move_to((cx + r, cy));
+        for p in [(cx, cy + r), (cx - r, cy), (cx, cy - r), (cx + r, cy)] {
+            painter.arc_to((r, r), 0.0, false, true, p);
+        }
+        painter.close();
+        painter.finish()
+    }
+
+    #[test]
+    fn boolean_squares() {
+        let a = square(0.0, 0.0, 2.0);
+        let b = square(1.0, 1.0, 2.0);
+
+        let union = a.union(&b);
+        assert_eq!(union.len(), 1);
+        assert_eq!(union.subpaths()[0].len(), 8);
+        assert_eq!(union.area(), 7.0);
+
+        let intersection = a.intersection(&b);
+        assert_eq!(intersection.len(), 1);
+        assert_eq!(intersection.subpaths()[0].len(), 4);
+        assert_eq!(intersection.area(), 1.0);
+        assert_eq!(intersection.centroid(), Some((1.5, 1.5)));
+
+        assert_eq!(a.difference(&b).area(), 3.0);
+        assert_eq!(b.difference(&a).area(), 3.0);
+        assert_eq!(a.xor(&b).area(), 6.0);
+
+        // disjoint and nested
+        let c = square(5.0, 0.0, 1.0);
+        assert_eq!(a.union(&c).len(), 2);
+        assert!(a.intersection(&c).is_empty());
+        let hole = a.difference(&square(0.5, 0.5, 1.0));
+        assert_eq!(hole.len(), 2);
+        assert_eq!(hole.area(), 3.0);
+        assert!(!hole.contains(&(1.0, 1.0), FillRule::NonZero));
+        assert!(hole.contains(&(0.25, 1.0), FillRule::NonZero));
+    }
+
+    #[test]
+    fn boolean_shared_edge() {
+        // two squares side by side, sharing the edge x = 2
+        let a = square(0.0, 0.0, 2.0);
+        let b = square(2.0, 0.0, 2.0);
+
+        let union = a.union(&b);
+        assert_eq!(union.len(), 1);
+        assert_eq!(union.area(), 8.0);
+
+        assert!(a.intersection(&b).is_empty());
+
+        let difference = a.difference(&b);
+        assert_eq!(difference.len(), 1);
+        assert_eq!(difference.area(), 4.0);
+
+        // the same shape
+        let union = a.union(&a);
+        assert_eq!(union.len(), 1);
+        assert_eq!(union.area(), 4.0);
+    }
+
+    #[test]
+    fn boolean_curves_keep_geometry() {
+        let a = circle(0.0, 0.0, 2.0);
+        let b = circle(2.0, 0.0, 2.0);
+
+        let union = a.union(&b);
+        let intersection = a.intersection(&b);
+        assert_eq!(union.len(), 1);
+        assert_eq!(intersection.len(), 1);
+
+        // inclusion exclusion holds up to floating error, as no curve is approximated
+        let sum = a.area() + b.area();
+        assert!((union.area() + intersection.area() - sum).abs() < 1e-9);
+        let xor = a.xor(&b);
+        assert!((xor.area() + 2.0 * intersection.area() - sum).abs() < 1e-9);
+
+        // only the two crossing points are corners, the others are still smooth
+        let points = union.subpaths()[0].points();
+        let corners = points
+            .iter()
+            .filter(|p| matches!(p, CurvePoint::Corner(_)))
+            .count();
+        assert_eq!(corners, 2);
+        assert!(points.len() > corners);
+
+        // every point of result is on the outline of one of the inputs
+        for p in points {
+            let d = [&a, &b]
+                .into_iter()
+                .filter_map(|s| s.nearest_point_on_curves(p.point(), true))
+                .map(|n| n.distance)
+                .fold(f64::INFINITY, f64::min);
+            assert!(d < 1e-9);
+        }
+    }
+
+    #[test]
+    fn boolean_arcs() {
+        let (r, d) = (2.0, 3.0);
+        let a = arc_circle(0.0, 0.0, r);
+        let b = arc_circle(d, 0.0, r);
+
+        // area of the lens is known in closed form
+        let lens =
+            2.0 * r * r * libm::acos(d / (2.0 * r)) - d / 2.0 * libm::sqrt(4.0 * r * r - d * d);
+        let intersection = a.intersection(&b);
+        assert_eq!(intersection.len(), 1);
+        assert!((intersection.area() - lens).abs() < 1e-9);
+        let union = a.union(&b);
+        assert_eq!(union.len(), 1);
+        assert!((union.area() - (2.0 * PI * r * r - lens)).abs() < 1e-9);
+
+        // fragments are still arcs
+        assert!(union.subpaths()[0]
+            .curves()
+            .all(|c| matches!(c, Curve::Arc(_))));
+    }
+
+    #[test]
+    fn boolean_tangent() {
+        // circles touching at (2, 0), no fragment of zero extent or contour of a single point
+        for (a, b) in [
+            (circle(0.0, 0.0, 2.0), circle(4.0, 0.0, 2.0)),
+            (arc_circle(0.0, 0.0, 2.0), arc_circle(4.0, 0.0, 2.0)),
+        ] {
+            let union = a.union(&b);
+            assert_eq!(union.len(), 2);
+            assert!(union.subpaths().iter().all(|s| s.len() >= 2));
+            assert!((union.area() - a.area() - b.area()).abs() < 1e-9);
+
+            assert!(a.intersection(&b).is_empty());
+            let difference = a.difference(&b);
+            assert_eq!(difference.len(), 1);
+            assert!((difference.area() - a.area()).abs() < 1e-9);
+        }
+    }
+}
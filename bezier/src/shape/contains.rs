@@ -0,0 +1,254 @@
+use crate::{Arc, Bezier, Curve, Point2D, Poly, Root, Segment, Shape};
+
+// Bisection steps to find where a monotonic piece of arc crosses the ray, enough to reach the
+// floating point precision of parameter.
+const ARC_BISECTION_STEPS: usize = 64;
+
+/// Rule to decide whether a point is inside a shape from its winding number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FillRule {
+    /// Inside if the winding number is not zero.
+    #[default]
+    NonZero,
+    /// Inside if the winding number is odd.
+    EvenOdd,
+}
+
+impl FillRule {
+    /// Check if a winding number is inside under this rule.
+    pub fn is_inside(&self, winding: i32) -> bool {
+        match self {
+            Self::NonZero => winding != 0,
+            Self::EvenOdd => winding % 2 != 0,
+        }
+    }
+}
+
+// Winding contribution of an edge going from y0 to y1 monotonically, which crosses the horizontal
+// line of target at `x`.
+//
+// Interval is half open, [min, max), so the shared endpoint of two connected edges is counted
+// only once.
+fn crossing(y0: f64, y1: f64, target_y: f64) -> Option<i32> {
+    if y0 < y1 && y0 <= target_y && target_y < y1 {
+        Some(1)
+    } else if y1 < y0 && y1 <= target_y && target_y < y0 {
+        Some(-1)
+    } else {
+        None
+    }
+}
+
+// Winding number contribution of a segment, by a ray from target to the positive x direction.
+fn segment_winding<P: Point2D>(s: &Segment<P>, target: &P) -> i32 {
+    let Some(dir) = crossing(s.start().y(), s.end().y(), target.y()) else {
+        return 0;
+    };
+
+    let t = (target.y() - s.start().y()) / (s.end().y() - s.start().y());
+    let x = s.start().x() + (s.end().x() - s.start().x()) * t;
+
+    if x > target.x() {
+        dir
+    } else {
+        0
+    }
+}
+
+// Winding number contribution of a cubic curve, by a ray from target to the positive x direction.
+//
+// The curve is split into y monotonic pieces at roots of y'(t), each piece crosses the ray at most
+// once, where y(t) = target.y is solved.
+fn bezier_winding<P: Point2D>(b: &Bezier<P>, target: &P) -> i32 {
+    let [a, bb, c, d] = b.parametric_function_coefficients();
+
+    let dy: Poly = [3.0 * a.y(), 2.0 * bb.y(), c.y()].into_iter().collect();
+    let mut ts = vec![0.0];
+    if let Root::Roots(roots) = dy.real_roots_in(0.0..=1.0) {
+        ts.extend(roots.into_iter().filter(|t| 0.0 < *t && *t < 1.0));
+    }
+    ts.sort_by(f64::total_cmp);
+    ts.push(1.0);
+
+    let y: Poly = [a.y(), bb.y(), c.y(), d.y() - target.y()]
+        .into_iter()
+        .collect();
+    let f = b.parametric_function();
+
+    let mut winding = 0;
+    for w in ts.windows(2) {
+        let (t0, t1) = (w[0], w[1]);
+        let (y0, y1) = (f(t0).y(), f(t1).y());
+
+        let Some(dir) = crossing(y0, y1, target.y()) else {
+            continue;
+        };
+
+        let t = match y.real_roots_in(t0..=t1) {
+            Root::Roots(roots) => roots[0],
+            // floating point error near the ends of piece
+            Root::None | Root::Any => {
+                if libm::fabs(y0 - target.y()) <= libm::fabs(y1 - target.y()) {
+                    t0
+                } else {
+                    t1
+                }
+            }
+        };
+
+        if f(t).x() > target.x() {
+            winding += dir;
+        }
+    }
+
+    winding
+}
+
+// Winding number contribution of an elliptical arc, by a ray from target to the positive x
+// direction.
+//
+// Like cubic curve, it's split into y monotonic pieces at its extrema, and the crossing point on
+// each piece is found by bisection.
+fn arc_winding<P: Point2D>(a: &Arc<P>, target: &P) -> i32 {
+    let mut ts = vec![0.0];
+    ts.extend(a.extrema().into_iter().filter(|t| 0.0 < *t && *t < 1.0));
+    ts.push(1.0);
+
+    let f = a.parametric_function();
+
+    let mut winding = 0;
+    for w in ts.windows(2) {
+        let (y0, y1) = (f(w[0]).y(), f(w[1]).y());
+
+        let Some(dir) = crossing(y0, y1, target.y()) else {
+            continue;
+        };
+
+        // keep y(t0) and y(t1) at different sides of target
+        let (mut t0, mut t1) = (w[0], w[1]);
+        for _ in 0..ARC_BISECTION_STEPS {
+            let mid = (t0 + t1) / 2.0;
+            if (f(mid).y() < target.y()) == (y0 < y1) {
+                t0 = mid;
+            } else {
+                t1 = mid;
+            }
+        }
+
+        if f((t0 + t1) / 2.0).x() > target.x() {
+            winding += dir;
+        }
+    }
+
+    winding
+}
+
+fn curve_winding<P: Point2D>(c: &Curve<P>, target: &P) -> i32 {
+    match c {
+        Curve::Segment(s) => segment_winding(s, target),
+        Curve::Quad(q) => bezier_winding(&q.to_cubic(), target),
+        Curve::Bezier(b) => bezier_winding(b, target),
+        Curve::Arc(a) => arc_winding(a, target),
+    }
+}
+
+impl<P: Point2D> Shape<P> {
+    /// Winding number of the shape around `target`, positive when the shape goes around it
+    /// counterclockwise.
+    ///
+    /// An open shape is treated as closed by a line segment from its last point to the first
+    /// one, as how it's filled.
+    pub fn winding(&self, target: &P) -> i32 {
+        let mut winding: i32 = self.curves().map(|c| curve_winding(&c, target)).sum();
+
+        if !self.closed() && self.len() >= 2 {
+            let points = self.points();
+            let closing = Segment::new(
+                points[points.len() - 1].point().clone(),
+                points[0].point().clone(),
+            );
+            winding += segment_winding(&closing, target);
+        }
+
+        winding
+    }
+
+    /// Check if `target` is inside the shape under the fill rule.
+    ///
+    /// Points exactly on the outline may be reported as either inside or outside.
+    pub fn contains(&self, target: &P, rule: FillRule) -> bool {
+        rule.is_inside(self.winding(target))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ShapePainter;
+
+    #[test]
+    fn contains_square_with_rules() {
+        // outer square counterclockwise, inner one in the same direction
+        let path = ["M0 0 L10 0 L10 10 L0 10 Z", "M3 3 L7 3 L7 7 L3 7 Z"]
+            .map(|d| Shape::<(f64, f64)>::parse_svg_path(d).unwrap());
+
+        let target = (5.0, 5.0);
+        let winding: i32 = path.iter().map(|s| s.winding(&target)).sum();
+        assert_eq!(winding, 2);
+        assert!(FillRule::NonZero.is_inside(winding));
+        assert!(!FillRule::EvenOdd.is_inside(winding));
+
+        assert!(path[0].contains(&(1.0, 1.0), FillRule::EvenOdd));
+        assert!(!path[0].contains(&(11.0, 5.0), FillRule::NonZero));
+        // ray passes through vertices
+        assert!(path[0].contains(&(5.0, 0.0), FillRule::NonZero));
+        assert!(!path[0].contains(&(-1.0, 10.0), FillRule::NonZero));
+    }
+
+    #[test]
+    fn contains_curves() {
+        // figure eight made of two cubic curves, two lobes wind in opposite directions
+        let mut painter = ShapePainter::new();
+        painter
+            .move_to((0.0, 0.0))
+            .cubic_to((10.0, 10.0), (10.0, -10.0), (0.0, 0.0))
+            .cubic_to((-10.0, 10.0), (-10.0, -10.0), (0.0, 0.0))
+            .close();
+        let shape = painter.finish();
+
+        // right lobe is clockwise, left one counterclockwise
+        for (target, winding) in [
+            ((5.0, 1.0), -1),
+            ((-5.0, 1.0), 1),
+            ((5.0, -1.0), -1),
+            ((0.0, 5.0), 0),
+            ((2.0, 0.0), -1),
+            ((8.0, 0.0), 0),
+        ] {
+            assert_eq!(shape.winding(&target), winding, "{target:?}");
+        }
+
+        // exact arcs, points just inside and outside the circle
+        let shape = Shape::parse_svg_path("M10 0 A10 10 0 0 1 0 10 L0 0 Z").unwrap();
+        let (sin, cos) = libm::sincos(0.3);
+        for (r, winding) in [(10.0 - 1e-9, 1), (10.0 + 1e-9, 0), (5.0, 1)] {
+            assert_eq!(shape.winding(&(r * cos, r * sin)), winding, "{r}");
+        }
+        // three quarters of a disk, counterclockwise
+        let shape = Shape::parse_svg_path("M10 0 A10 10 0 1 1 0 -10 L0 0 Z").unwrap();
+        for (target, winding) in [
+            ((-5.0, 0.0), 1),
+            ((0.0, 5.0), 1),
+            ((-7.0, -7.0), 1),
+            ((-8.0, -8.0), 0),
+            ((5.0, -5.0), 0),
+        ] {
+            assert_eq!(shape.winding(&target), winding, "{target:?}");
+        }
+
+        // open shape is closed implicitly
+        let shape = Shape::parse_svg_path("M0 0 C0 10 10 10 10 0").unwrap();
+        assert!(shape.contains(&(5.0, 3.0), FillRule::NonZero));
+        assert!(!shape.contains(&(5.0, -1.0), FillRule::NonZero));
+    }
+}
@@ -0,0 +1,278 @@
+use alloc::vec::Vec;
+
+use super::ShapePainter;
+use crate::{Curve, Point2D, Segment, Shape};
+
+// Part of the curve in [t0, t1].
+pub(super) fn sub_curve<P: Point2D>(curve: &Curve<P>, t0: f64, t1: f64) -> Curve<P> {
+    match curve {
+        Curve::Segment(l) => Curve::Segment(Segment::new(l.at(t0), l.at(t1))),
+        Curve::Quad(q) => {
+            let head = if t1 < 1.0 {
+                q.split_at(t1).0
+            } else {
+                q.clone()
+            };
+
+            if t0 <= 0.0 {
+                Curve::Quad(head)
+            } else {
+                Curve::Quad(head.split_at((t0 / t1).min(1.0)).1)
+            }
+        }
+        Curve::Bezier(b) => {
+            let head = if t1 < 1.0 {
+                b.split_at(t1).0
+            } else {
+                b.clone()
+            };
+
+            if t0 <= 0.0 {
+                Curve::Bezier(head)
+            } else {
+                Curve::Bezier(head.split_at((t0 / t1).min(1.0)).1)
+            }
+        }
+        Curve::Arc(a) => {
+            let (_, tail) = a.split_at(t0);
+            let (part, _) = tail.split_at(((t1 - t0) / (1.0 - t0)).min(1.0));
+            Curve::Arc(part)
+        }
+    }
+}
+
+// Position in the dash pattern.
+struct Dasher<'a> {
+    pattern: &'a [f64],
+    index: usize,
+    remain: f64,
+}
+
+impl<'a> Dasher<'a> {
+    fn new(pattern: &'a [f64], offset: f64) -> Self {
+        let total: f64 = pattern.iter().sum();
+        let mut phase = libm::fmod(offset, total);
+        if phase < 0.0 {
+            phase += total;
+        }
+
+        let mut dasher = Self {
+            pattern,
+            index: 0,
+            remain: pattern[0],
+        };
+
+        for _ in 0..pattern.len() {
+            if phase < dasher.remain {
+                break;
+            }
+            phase -= dasher.remain;
+            dasher.advance();
+        }
+        dasher.remain -= phase;
+
+        dasher
+    }
+
+    fn on(&self) -> bool {
+        self.index.is_multiple_of(2)
+    }
+
+    fn advance(&mut self) {
+        self.index = (self.index + 1) % self.pattern.len();
+        self.remain = self.pattern[self.index];
+    }
+}
+
+fn paint<P: Point2D>(curves: &[Curve<P>]) -> Shape<P> {
+    let mut painter = ShapePainter::new();
+    painter.move_to(curves[0].at(0.0));
+
+    for c in curves {
+        painter.curve_to(c);
+    }
+
+    painter.finish()
+}
+
+impl<P: Point2D> Shape<P> {
+    /// Split the shape into open sub shapes for the "on" intervals of a dash pattern.
+    ///
+    /// `pattern` is lengths of alternating on and off intervals, repeated twice if it has odd
+    /// count of items, same as SVG `stroke-dasharray`. `offset` is the distance into the pattern
+    /// where the shape starts. Arc length is calculated with `tolerance`.
+    ///
+    /// For closed shape, the dash crossing or touching the start point is kept as one sub shape.
+    /// Zero length dashes are skipped. If pattern is empty or invalid, which has negative value or
+    /// sum not greater than `tolerance`, the whole shape is returned as is.
+    ///
+    /// Count of dashes is the shape length divided by the pattern sum, so a tiny pattern on a long
+    /// shape is still expensive.
+    pub fn dash(&self, pattern: &[f64], offset: f64, tolerance: f64) -> Vec<Self> {
+        let mut pattern = pattern.to_vec();
+        if !pattern.len().is_multiple_of(2) {
+            pattern.extend_from_within(..);
+        }
+
+        let total: f64 = pattern.iter().sum();
+        if pattern.is_empty()
+            || pattern.iter().any(|d| *d < 0.0)
+            || total <= tolerance.max(0.0)
+            || !total.is_finite()
+        {
+            return vec![self.clone()];
+        }
+
+        let mut dasher = Dasher::new(&pattern, offset);
+        let starts_on = dasher.on();
+
+        let mut dashes: Vec<Vec<Curve<P>>> = Vec::new();
+        let mut current = Vec::new();
+        // whether the last dash ends exactly at the end of current curve
+        let mut at_end = false;
+
+        for curve in self.curves() {
+            let length = curve.length(tolerance);
+            let mut pos = 0.0;
+            if length > 0.0 {
+                at_end = false;
+            }
+
+            loop {
+                let on = dasher.on();
+
+                if pos + dasher.remain <= length {
+                    let end = pos + dasher.remain;
+
+                    if on {
+                        if end > pos {
+                            let t0 = curve.t_at_length(pos, tolerance);
+                            let t1 = curve.t_at_length(end, tolerance);
+                            current.push(sub_curve(&curve, t0, t1));
+                        }
+                        if !current.is_empty() {
+                            dashes.push(core::mem::take(&mut current));
+                            at_end = end >= length;
+                        }
+                    }
+
+                    pos = end;
+                    dasher.advance();
+                } else {
+                    if on && length > pos {
+                        let t0 = curve.t_at_length(pos, tolerance);
+                        current.push(sub_curve(&curve, t0, 1.0));
+                    }
+
+                    dasher.remain -= length - pos;
+                    break;
+                }
+            }
+        }
+
+        if self.closed() && starts_on {
+            // dash crossing the start point of closed shape, or ending exactly there
+            if !current.is_empty() && !dashes.is_empty() {
+                current.append(&mut dashes[0]);
+                dashes[0] = core::mem::take(&mut current);
+            } else if current.is_empty() && at_end && dashes.len() > 1 {
+                let mut last = dashes.pop().unwrap();
+                last.append(&mut dashes[0]);
+                dashes[0] = last;
+            }
+        }
+
+        if !current.is_empty() {
+            dashes.push(current);
+        }
+
+        dashes.iter().map(|d| paint(d)).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn square() -> Shape<(f64, f64)> {
+        let mut painter = ShapePainter::new();
+        painter
+            .move_to((0.0, 0.0))
+            .line_to((10.0, 0.0))
+            .line_to((10.0, 10.0))
+            .line_to((0.0, 10.0))
+            .close();
+        painter.finish()
+    }
+
+    #[test]
+    fn dash_line() {
+        let mut painter = ShapePainter::new();
+        painter.move_to((0.0, 0.0)).line_to((10.0, 0.0));
+        let shape = painter.finish();
+
+        let dashes = shape.dash(&[2.0, 1.0], 0.0, 1e-9);
+        let ranges: Vec<_> = dashes
+            .iter()
+            .map(|d| {
+                (
+                    d.points()[0].point().0,
+                    d.points().last().unwrap().point().0,
+                )
+            })
+            .collect();
+        assert_eq!(ranges, [(0.0, 2.0), (3.0, 5.0), (6.0, 8.0), (9.0, 10.0)]);
+        assert!(dashes.iter().all(|d| !d.closed()));
+
+        // odd count pattern is repeated, and offset moves into the pattern
+        let dashes = shape.dash(&[3.0], 4.0, 1e-9);
+        assert_eq!(dashes.len(), 2);
+        assert_eq!(dashes[0].points()[0].point(), &(2.0, 0.0));
+
+        assert_eq!(shape.dash(&[], 0.0, 1e-9).len(), 1);
+        assert_eq!(shape.dash(&[0.0, 0.0], 0.0, 1e-9).len(), 1);
+
+        // pattern below the arc length accuracy is rejected
+        assert_eq!(shape.dash(&[1e-12, 1e-12], 0.0, 1e-9).len(), 1);
+    }
+
+    #[test]
+    fn dash_closed() {
+        let dashes = square().dash(&[3.0, 2.0], 0.0, 1e-9);
+        assert_eq!(dashes.len(), 8);
+
+        // last dash is merged with the first one across the start point
+        let dashes = square().dash(&[3.0, 2.0], 1.0, 1e-9);
+        assert_eq!(dashes.len(), 8);
+        assert_eq!(dashes[0].points()[0].point(), &(0.0, 1.0));
+        assert_eq!(dashes[0].points().last().unwrap().point(), &(2.0, 0.0));
+        assert!((dashes[0].length(1e-9) - 3.0).abs() < 1e-9);
+
+        // last dash ends exactly at the start point, where the first one starts
+        let dashes = square().dash(&[4.0, 2.0], 0.0, 1e-9);
+        assert_eq!(dashes.len(), 6);
+        assert_eq!(dashes[0].points()[0].point(), &(0.0, 4.0));
+        assert_eq!(dashes[0].points().last().unwrap().point(), &(4.0, 0.0));
+        assert!((dashes[0].length(1e-9) - 8.0).abs() < 1e-9);
+
+        // whole shape in one dash is not merged with itself
+        assert_eq!(square().dash(&[40.0, 2.0], 0.0, 1e-9).len(), 1);
+    }
+
+    #[test]
+    fn dash_bezier() {
+        let mut painter = ShapePainter::new();
+        painter
+            .move_to((0.0, 0.0))
+            .cubic_to((0.0, 100.0), (300.0, 100.0), (300.0, 0.0));
+        let shape = painter.finish();
+        let total = shape.length(1e-9);
+
+        let dashes = shape.dash(&[10.0, 5.0], 0.0, 1e-9);
+        assert_eq!(dashes.len(), libm::ceil(total / 15.0) as usize);
+
+        for d in &dashes[..dashes.len() - 1] {
+            assert!((d.length(1e-9) - 10.0).abs() < 1e-6);
+        }
+    }
+}
@@ -0,0 +1,447 @@
+use alloc::vec::Vec;
+
+use super::ShapePainter;
+use crate::{Bezier, Point2D, Shape};
+
+// Max angle between the two chords at a sample, in degree, above which the sample is a corner.
+const CORNER_ANGLE: f64 = 60.0;
+
+// Max count of newton reparameterization before splitting the samples.
+const MAX_ITERATION: usize = 4;
+
+// Max count of newton reparameterization when fitting a single curve.
+const MAX_SINGLE_ITERATION: usize = 32;
+
+// If the error is within this multiple of tolerance, try reparameterization before splitting.
+const ITERATION_ERROR_FACTOR: f64 = 4.0;
+
+// Max recursion depth of splitting, bounds the stack usage when tolerance is tiny or zero.
+const MAX_DEPTH: usize = 32;
+
+// Unit tangent at the start of samples, pointing into the curve.
+fn start_tangent<P: Point2D>(samples: &[P]) -> P {
+    samples[1].minus(&samples[0]).normalize()
+}
+
+// Unit tangent at the end of samples, pointing into the curve.
+fn end_tangent<P: Point2D>(samples: &[P]) -> P {
+    let n = samples.len();
+    samples[n - 2].minus(&samples[n - 1]).normalize()
+}
+
+// Unit tangent at an inner sample, pointing backward.
+fn center_tangent<P: Point2D>(samples: &[P], i: usize) -> P {
+    let d = samples[i - 1].minus(&samples[i + 1]);
+
+    if d.length_from_origin() == 0.0 {
+        samples[i - 1].minus(&samples[i]).normalize()
+    } else {
+        d.normalize()
+    }
+}
+
+// Normalized accumulated chord length of each sample.
+fn chord_length_parameterize<P: Point2D>(samples: &[P]) -> Vec<f64> {
+    let mut u = Vec::with_capacity(samples.len());
+    u.push(0.0);
+
+    for w in samples.windows(2) {
+        u.push(u.last().unwrap() + w[0].distance(&w[1]));
+    }
+
+    let total = *u.last().unwrap();
+    u.iter_mut().for_each(|v| *v /= total);
+
+    u
+}
+
+// Least squares cubic with given endpoints and tangent directions, only the handle lengths are
+// solved.
+fn generate_bezier<P: Point2D>(samples: &[P], u: &[f64], t1: &P, t2: &P) -> Bezier<P> {
+    let first = &samples[0];
+    let last = &samples[samples.len() - 1];
+
+    let mut c = [[0.0; 2]; 2];
+    let mut x = [0.0; 2];
+
+    for (p, &t) in samples.iter().zip(u) {
+        let s = 1.0 - t;
+        let b0 = s * s * s;
+        let b1 = 3.0 * t * s * s;
+        let b2 = 3.0 * t * t * s;
+        let b3 = t * t * t;
+
+        let a1 = t1.scale(b1);
+        let a2 = t2.scale(b2);
+
+        c[0][0] += a1.dot(&a1);
+        c[0][1] += a1.dot(&a2);
+        c[1][1] += a2.dot(&a2);
+
+        let tmp = p.minus(&first.scale(b0 + b1)).minus(&last.scale(b2 + b3));
+        x[0] += a1.dot(&tmp);
+        x[1] += a2.dot(&tmp);
+    }
+    c[1][0] = c[0][1];
+
+    let det = c[0][0] * c[1][1] - c[1][0] * c[0][1];
+    let (alpha1, alpha2) = if det == 0.0 {
+        (0.0, 0.0)
+    } else {
+        (
+            (x[0] * c[1][1] - x[1] * c[0][1]) / det,
+            (c[0][0] * x[1] - c[1][0] * x[0]) / det,
+        )
+    };
+
+    // fall back to the heuristic when the solution is degenerated or handles point backward
+    let length = first.distance(last);
+    let epsilon = 1e-6 * length;
+    let (alpha1, alpha2) = if alpha1 < epsilon || alpha2 < epsilon {
+        (length / 3.0, length / 3.0)
+    } else {
+        (alpha1, alpha2)
+    };
+
+    Bezier::new(
+        first.clone(),
+        first.plus(&t1.scale(alpha1)),
+        last.plus(&t2.scale(alpha2)),
+        last.clone(),
+    )
+}
+
+// Max distance from samples to the curve at their parameters, and index of the farthest one.
+fn max_error<P: Point2D>(bezier: &Bezier<P>, samples: &[P], u: &[f64]) -> (f64, usize) {
+    let f = bezier.parametric_function();
+
+    samples
+        .iter()
+        .zip(u)
+        .enumerate()
+        .map(|(i, (p, &t))| (f(t).distance(p), i))
+        .fold((0.0, samples.len() / 2), |best, e| {
+            if e.0 > best.0 {
+                e
+            } else {
+                best
+            }
+        })
+}
+
+// One newton step of each parameter towards the nearest point on the curve.
+fn reparameterize<P: Point2D>(bezier: &Bezier<P>, samples: &[P], u: &mut [f64]) {
+    let f = bezier.parametric_function();
+
+    for (p, t) in samples.iter().zip(u.iter_mut()) {
+        let d = f(*t).minus(p);
+        let d1 = bezier.derivative(*t);
+        let d2 = bezier.second_derivative(*t);
+
+        let numerator = d.dot(&d1);
+        let denominator = d1.dot(&d1) + d.dot(&d2);
+
+        if denominator != 0.0 {
+            *t = (*t - numerator / denominator).clamp(0.0, 1.0);
+        }
+    }
+}
+
+// Straight cubic curve between two samples, with handles along the given tangents.
+fn straight<P: Point2D>(first: &P, last: &P, t1: &P, t2: &P) -> Bezier<P> {
+    let d = first.distance(last) / 3.0;
+
+    Bezier::new(
+        first.clone(),
+        first.plus(&t1.scale(d)),
+        last.plus(&t2.scale(d)),
+        last.clone(),
+    )
+}
+
+// Fit the samples with cubic curves recursively, push them into `result`.
+//
+// When `depth` runs out, the samples are connected one by one with straight curves instead, which
+// pass through all of them exactly, so the error is still within tolerance.
+fn fit_cubic<P: Point2D>(
+    samples: &[P], t1: &P, t2: &P, tolerance: f64, depth: usize, result: &mut Vec<Bezier<P>>,
+) {
+    let first = &samples[0];
+    let last = &samples[samples.len() - 1];
+
+    if samples.len() == 2 {
+        result.push(straight(first, last, t1, t2));
+        return;
+    }
+
+    let mut u = chord_length_parameterize(samples);
+    let mut bezier = generate_bezier(samples, &u, t1, t2);
+    let (mut error, mut split) = max_error(&bezier, samples, &u);
+
+    if error <= tolerance {
+        result.push(bezier);
+        return;
+    }
+
+    if depth == 0 {
+        for w in samples.windows(2) {
+            let chord = w[1].minus(&w[0]).normalize();
+            result.push(straight(&w[0], &w[1], &chord, &chord.negative()));
+        }
+        return;
+    }
+
+    if error <= tolerance * ITERATION_ERROR_FACTOR {
+        for _ in 0..MAX_ITERATION {
+            reparameterize(&bezier, samples, &mut u);
+            bezier = generate_bezier(samples, &u, t1, t2);
+            (error, split) = max_error(&bezier, samples, &u);
+
+            if error <= tolerance {
+                result.push(bezier);
+                return;
+            }
+        }
+    }
+
+    // split at the farthest sample, which is always an inner one as endpoints are interpolated
+    let split = split.clamp(1, samples.len() - 2);
+    let center = center_tangent(samples, split);
+
+    fit_cubic(
+        &samples[..=split],
+        t1,
+        &center,
+        tolerance,
+        depth - 1,
+        result,
+    );
+    fit_cubic(
+        &samples[split..],
+        &center.negative(),
+        t2,
+        tolerance,
+        depth - 1,
+        result,
+    );
+}
+
+// Best single cubic curve for the samples with given end tangents, and max distance from the
+// samples to it.
+pub(super) fn fit_single<P: Point2D>(samples: &[P], t1: &P, t2: &P) -> (Bezier<P>, f64) {
+    let mut u = chord_length_parameterize(samples);
+    let mut bezier = generate_bezier(samples, &u, t1, t2);
+    let (mut error, _) = max_error(&bezier, samples, &u);
+
+    for _ in 0..MAX_SINGLE_ITERATION {
+        reparameterize(&bezier, samples, &mut u);
+        let next = generate_bezier(samples, &u, t1, t2);
+        let (e, _) = max_error(&next, samples, &u);
+
+        if e >= error {
+            break;
+        }
+        (bezier, error) = (next, e);
+    }
+
+    // error at parameters is only an upper bound, measure the real distance
+    let error = samples
+        .iter()
+        .filter_map(|p| bezier.nearest_to(p, true))
+        .map(|n| n.distance)
+        .fold(0.0, f64::max);
+
+    (bezier, error)
+}
+
+// Check if the sample turns sharply, which should not be smoothed.
+fn is_corner<P: Point2D>(prev: &P, p: &P, next: &P) -> bool {
+    let a = p.minus(prev).normalize();
+    let b = next.minus(p).normalize();
+
+    a.dot(&b) < libm::cos(CORNER_ANGLE.to_radians())
+}
+
+impl<P: Point2D> Shape<P> {
+    /// Fit ordered samples, such as freehand input or a polyline, with cubic bezier curves. The
+    /// distance from each sample to the result is within `tolerance`.
+    ///
+    /// Samples are split recursively until each piece fits, down to a depth limit. Pieces still
+    /// out of tolerance there are connected sample by sample with straight curves, which keeps the
+    /// bound but loses smoothness, this only happens with a tiny tolerance on many samples.
+    ///
+    /// This is the algorithm of Philip J. Schneider, "An Algorithm for Automatically Fitting
+    /// Digitized Curves", Graphics Gems, 1990. Samples where the direction turns sharply are kept
+    /// as [CornerPoint], and joints inside a smooth run become [SmoothPoint].
+    ///
+    /// If the first and last samples are the same point, the shape is closed.
+    ///
+    /// [CornerPoint]: crate::CornerPoint
+    /// [SmoothPoint]: crate::SmoothPoint
+    pub fn fit(samples: &[P], tolerance: f64) -> Self {
+        let mut points: Vec<P> = Vec::with_capacity(samples.len());
+        for p in samples {
+            if points.last().is_none_or(|last| last.distance(p) != 0.0) {
+                points.push(p.clone());
+            }
+        }
+
+        let mut painter = ShapePainter::new();
+
+        let Some(first) = points.first() else {
+            return painter.finish();
+        };
+        painter.move_to(first.clone());
+
+        let n = points.len();
+        let closed = n > 3 && first.distance(&points[n - 1]) == 0.0;
+
+        // split samples at corners, each run is fitted separately
+        let mut runs = vec![0];
+        runs.extend(
+            (1..n.saturating_sub(1))
+                .filter(|&i| is_corner(&points[i - 1], &points[i], &points[i + 1])),
+        );
+        runs.push(n - 1);
+
+        // start point of a closed shape is smooth unless it's a corner
+        let wrap = (closed && !is_corner(&points[n - 2], first, &points[1])).then(|| {
+            center_tangent(
+                &[points[n - 2].clone(), first.clone(), points[1].clone()],
+                1,
+            )
+        });
+
+        let mut curves = Vec::new();
+        for (k, w) in runs.windows(2).enumerate() {
+            let run = &points[w[0]..=w[1]];
+            if run.len() < 2 {
+                continue;
+            }
+
+            let t1 = match &wrap {
+                Some(c) if k == 0 => c.negative(),
+                _ => start_tangent(run),
+            };
+            let t2 = match &wrap {
+                Some(c) if k + 2 == runs.len() => c.clone(),
+                _ => end_tangent(run),
+            };
+            fit_cubic(run, &t1, &t2, tolerance, MAX_DEPTH, &mut curves);
+        }
+
+        for b in curves {
+            painter.cubic_to(b.ctrl1, b.ctrl2, b.end);
+        }
+
+        if closed {
+            painter.close();
+        }
+
+        painter.finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::CurvePoint;
+
+    #[test]
+    fn fit_within_tolerance() {
+        let samples: Vec<_> = (0..=100)
+            .map(|i| {
+                let x = i as f64 / 10.0;
+                (x, libm::sin(x))
+            })
+            .collect();
+
+        for tolerance in [0.1, 0.01, 0.001] {
+            let shape = Shape::fit(&samples, tolerance);
+            assert!(!shape.closed());
+            assert!(shape
+                .points()
+                .iter()
+                .skip(1)
+                .take(shape.len() - 2)
+                .all(|p| matches!(p, CurvePoint::Smooth(_))));
+
+            for p in &samples {
+                let d = shape.nearest_point_on_curves(p, true).unwrap().distance;
+                assert!(d <= tolerance, "{d} > {tolerance}");
+            }
+        }
+
+        assert!(Shape::fit(&samples, 0.001).len() > Shape::fit(&samples, 0.1).len());
+    }
+
+    #[test]
+    fn fit_depth_limit() {
+        let samples: Vec<_> = (0..=20)
+            .map(|i| {
+                let x = i as f64 / 2.0;
+                (x, libm::sin(x))
+            })
+            .collect();
+        let (t1, t2) = (start_tangent(&samples), end_tangent(&samples));
+
+        let mut curves = Vec::new();
+        fit_cubic(&samples, &t1, &t2, 1e-12, 1, &mut curves);
+
+        let mut painter = ShapePainter::new();
+        painter.move_to(samples[0]);
+        for b in curves {
+            painter.cubic_to(b.ctrl1, b.ctrl2, b.end);
+        }
+        let shape = painter.finish();
+
+        for p in &samples {
+            let d = shape.nearest_point_on_curves(p, true).unwrap().distance;
+            assert!(d <= 1e-12, "{d}");
+        }
+    }
+
+    #[test]
+    fn fit_closed_smooth() {
+        let mut samples: Vec<_> = (0..64)
+            .map(|i| {
+                let (sin, cos) = libm::sincos(i as f64 / 64.0 * core::f64::consts::TAU);
+                (10.0 * cos, 10.0 * sin)
+            })
+            .collect();
+        samples.push(samples[0]);
+
+        let shape = Shape::fit(&samples, 0.01);
+        assert!(shape.closed());
+        assert!(shape
+            .points()
+            .iter()
+            .all(|p| matches!(p, CurvePoint::Smooth(_))));
+    }
+
+    #[test]
+    fn fit_corners() {
+        // closed square, each side sampled with 10 points
+        let corners = [(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)];
+        let mut samples = Vec::new();
+        for i in 0..4 {
+            let (a, b) = (corners[i], corners[(i + 1) % 4]);
+            for j in 0..10 {
+                let t = j as f64 / 10.0;
+                samples.push((a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t));
+            }
+        }
+        samples.push((0.0, 0.0));
+
+        let shape = Shape::fit(&samples, 0.01);
+        assert!(shape.closed());
+        assert_eq!(shape.len(), 4);
+        assert!(shape
+            .points()
+            .iter()
+            .all(|p| matches!(p, CurvePoint::Corner(_))));
+        for (p, c) in shape.points().iter().zip(corners) {
+            assert!(p.point().distance(&c) < 1e-12);
+        }
+    }
+}
@@ -0,0 +1,569 @@
+mod boolean;
+mod contains;
+mod dash;
+mod fit;
+mod moment;
+mod painter;
+mod stroke;
+
+use alloc::vec::Vec;
+
+pub use self::{
+    boolean::BooleanOp,
+    contains::FillRule,
+    moment::{Orientation, SecondMoments},
+    painter::ShapePainter,
+    stroke::{LineCap, LineJoin},
+};
+use crate::{Arc, CornerPoint, Curve, CurvePoint, Nearest, Point2D, Rect, Segment, SmoothPoint};
+
+// Samples taken on each of the two curves around a removed point.
+const REMOVE_SAMPLES: usize = 32;
+
+// Max distance between centers or radii of two arcs, relative to the radii, to be merged as the
+// same ellipse.
+const ARC_MERGE_EPSILON: f64 = 1e-9;
+
+// Slack when pruning by bounding box, make sure floating error never skips the real nearest one.
+const PRUNE_EPSILON: f64 = 1e-9;
+
+#[derive(Default, Clone)]
+pub struct Shape<P> {
+    points: Vec<CurvePoint<P>>,
+    close: bool,
+}
+
+impl<P> Shape<P> {
+    pub fn closed(&self) -> bool {
+        self.close
+    }
+
+    pub fn set_close(&mut self, val: bool) {
+        self.close = val;
+    }
+
+    pub fn toggle_close(&mut self) {
+        self.close = !self.close;
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn points(&self) -> &[CurvePoint<P>] {
+        &self.points
+    }
+
+    pub fn points_mut(&mut self) -> &mut [CurvePoint<P>] {
+        &mut self.points
+    }
+
+    pub fn with_points<F>(&mut self, f: F)
+    where
+        F: FnOnce(&mut Vec<CurvePoint<P>>),
+    {
+        f(&mut self.points)
+    }
+
+    pub fn push(&mut self, point: CurvePoint<P>) {
+        self.points.push(point);
+    }
+
+    pub fn insert(&mut self, index: usize, point: CurvePoint<P>) {
+        self.points.insert(index, point);
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        self.points.remove(index);
+    }
+
+    pub fn replace(&mut self, index: usize, point: CurvePoint<P>) {
+        self.points[index] = point;
+    }
+}
+
+impl<P: Point2D> Shape<P> {
+    pub fn curves(&self) -> impl Iterator<Item = Curve<P>> + '_ {
+        let mut close_returned = false;
+
+        self.points
+            .windows(2)
+            .map(|curve| Curve::new(&curve[0], &curve[1]))
+            .chain(core::iter::from_fn(move || {
+                if !close_returned && self.close && self.points.len() >= 2 {
+                    close_returned = true;
+                    Some(Curve::new(
+                        self.points.last().unwrap(),
+                        self.points.first().unwrap(),
+                    ))
+                } else {
+                    None
+                }
+            }))
+    }
+
+    pub fn insert_on_curve(&mut self, index: usize, t: f64) {
+        let curve = self.curves().nth(index).unwrap();
+        let target = curve.at(t);
+
+        match curve {
+            Curve::Bezier(b) => {
+                let l = self.points.len();
+
+                // split the curve at t
+                let (left, right) = b.split_at(t);
+
+                // adjust around point's ctrl points
+                self.points[index].update_out_ctrl(left.ctrl1);
+                self.points[(index + 1) % l].update_in_ctrl(right.ctrl2);
+
+                // create new point
+                let mut p = SmoothPoint::horizontal(target, 1.0, 1.0);
+                p.move_in_ctrl_to(&left.ctrl2);
+                p.move_out_ctrl_to(&right.ctrl1);
+
+                // insert
+                self.insert(index + 1, p.into());
+            }
+            Curve::Quad(q) => {
+                let l = self.points.len();
+
+                let (left, right) = q.split_at(t);
+
+                // keep the ctrl point on the same side as origin curve, so both parts stay
+                // quadratic
+                if self.points[index].out_ctrl().is_some() {
+                    self.points[index].update_out_ctrl(left.ctrl);
+                    let p = CornerPoint::new(target).with_out_ctrl(right.ctrl);
+                    self.insert(index + 1, p.into());
+                } else {
+                    self.points[(index + 1) % l].update_in_ctrl(right.ctrl);
+                    let p = CornerPoint::new(target).with_in_ctrl(left.ctrl);
+                    self.insert(index + 1, p.into());
+                }
+            }
+            Curve::Segment(_) => self.insert(index + 1, CornerPoint::new(target).into()),
+            Curve::Arc(a) => {
+                let (left, right) = a.split_at(t);
+
+                // both parts are on the same ellipse, only the large arc flag may change
+                self.points[index].update_out_arc(left.params());
+                let p = CornerPoint::new(target).with_out_arc(right.params());
+                self.insert(index + 1, p.into());
+            }
+        }
+    }
+
+    /// Remove the point at `index`, and refit the two curves around it into one, which is the
+    /// inverse of [Shape::insert_on_curve].
+    ///
+    /// Directions at both neighbour points are kept, only handle lengths are changed to match the
+    /// original geometry best. If both curves are line segments, they become one segment, and if
+    /// both are arcs of the same ellipse in the same direction, they become one arc.
+    ///
+    /// Returns the max distance from the original curves to the new one, or `None` without
+    /// changing the shape if the point is an endpoint of an open shape, or there are too few
+    /// points to form a curve after removal.
+    pub fn remove_on_curve(&mut self, index: usize) -> Option<f64> {
+        let l = self.points.len();
+        if l < 3 || !self.close && (index == 0 || index + 1 == l) {
+            return None;
+        }
+
+        let prev = (index + l - 1) % l;
+        let next = (index + 1) % l;
+
+        let c1 = Curve::new(&self.points[prev], &self.points[index]);
+        let c2 = Curve::new(&self.points[index], &self.points[next]);
+
+        if let (Curve::Segment(a), Curve::Segment(b)) = (&c1, &c2) {
+            let error = Segment::new(a.start().clone(), b.end().clone())
+                .nearest_to(a.end(), true)
+                .map_or(0.0, |n| n.distance);
+            self.points.remove(index);
+            return Some(error);
+        }
+
+        if let (Curve::Arc(a), Curve::Arc(b)) = (&c1, &c2) {
+            let size = a.radii.length_from_origin();
+            if a.center.distance(&b.center) <= ARC_MERGE_EPSILON * size
+                && a.radii.distance(&b.radii) <= ARC_MERGE_EPSILON * size
+                && a.rotation == b.rotation
+                && (a.sweep_angle > 0.0) == (b.sweep_angle > 0.0)
+                && libm::fabs(a.sweep_angle + b.sweep_angle) < 360.0
+            {
+                let merged = Arc::new(
+                    a.center.clone(),
+                    a.radii.clone(),
+                    a.rotation,
+                    a.start_angle,
+                    a.sweep_angle + b.sweep_angle,
+                );
+                let error = merged
+                    .nearest_to(self.points[index].point(), true)
+                    .map_or(0.0, |n| n.distance);
+                self.points[prev].update_out_arc(merged.params());
+                self.points.remove(index);
+                return Some(error);
+            }
+        }
+
+        let samples: Vec<P> = (0..REMOVE_SAMPLES)
+            .map(|i| c1.at(i as f64 / REMOVE_SAMPLES as f64))
+            .chain((0..=REMOVE_SAMPLES).map(|i| c2.at(i as f64 / REMOVE_SAMPLES as f64)))
+            .collect();
+
+        let (bezier, error) =
+            fit::fit_single(&samples, &c1.tangent(0.0), &c2.tangent(1.0).negative());
+
+        self.points[prev].update_out_ctrl(bezier.ctrl1);
+        self.points[next].update_in_ctrl(bezier.ctrl2);
+        self.points.remove(index);
+
+        Some(error)
+    }
+
+    /// Exact bounding box of the shape, `None` if shape is empty.
+    pub fn bounds(&self) -> Option<Rect<P>> {
+        let points = Rect::from_points(self.points.iter().map(|p| p.point()))?;
+        Some(
+            self.curves()
+                .fold(points, |rect, c| rect.union(&c.bounds())),
+        )
+    }
+
+    /// Bounding box of all points and their control points, `None` if shape is empty. Arcs have no
+    /// control points, their exact bounds are included.
+    pub fn control_bounds(&self) -> Option<Rect<P>> {
+        let mut rect = Rect::from_points(self.points.iter().map(|p| p.point()))?;
+
+        for curve in self.curves() {
+            if let Curve::Arc(a) = curve {
+                rect = rect.union(&a.bounds());
+            }
+        }
+
+        for p in &self.points {
+            if let Some(c) = p.in_ctrl() {
+                rect.include(&c);
+            }
+            if let Some(c) = p.out_ctrl() {
+                rect.include(&c);
+            }
+        }
+
+        Some(rect)
+    }
+
+    /// Total arc length of all curves, `tolerance` is the max error allowed for each curve.
+    pub fn length(&self, tolerance: f64) -> f64 {
+        self.curves().map(|c| c.length(tolerance)).sum()
+    }
+
+    /// Find the curve index and t on it where the arc length from start point of the shape is
+    /// `s`. Length out of range is clamped to the start or end of shape.
+    ///
+    /// Returns `None` if shape has no curve.
+    pub fn t_at_length(&self, s: f64, tolerance: f64) -> Option<(usize, f64)> {
+        let mut remain = s;
+        let mut last = None;
+
+        for (i, curve) in self.curves().enumerate() {
+            if remain <= 0.0 {
+                return Some((i, 0.0));
+            }
+
+            let length = curve.length(tolerance);
+            if remain <= length {
+                return Some((i, curve.t_at_length(remain, tolerance)));
+            }
+
+            remain -= length;
+            last.replace(i);
+        }
+
+        last.map(|i| (i, 1.0))
+    }
+
+    /// Polyline approximates the shape, with max deviation `tolerance`.
+    ///
+    /// Endpoints shared by adjacent curves appear once. For closed shape, the last point is the
+    /// same as the first one. See [Bezier::flatten](crate::Bezier::flatten) for the min tolerance.
+    pub fn flatten(&self, tolerance: f64) -> Vec<P> {
+        let mut points: Vec<P> = self
+            .points
+            .first()
+            .map(|p| p.point().clone())
+            .into_iter()
+            .collect();
+
+        for curve in self.curves() {
+            curve.flatten_into(tolerance, &mut points);
+        }
+
+        points
+    }
+
+    /// Shape with each cubic curve or arc approximated by a chain of quadratic curves within
+    /// `tolerance`, line segments are kept as is.
+    pub fn to_quads(&self, tolerance: f64) -> Self {
+        let mut painter = ShapePainter::new();
+
+        if let Some(first) = self.points.first() {
+            painter.move_to(first.point().clone());
+        }
+
+        for curve in self.curves() {
+            match curve {
+                Curve::Segment(l) => {
+                    painter.line_to(l.end().clone());
+                }
+                Curve::Bezier(b) => {
+                    for q in b.to_quads(tolerance) {
+                        painter.quad_to(q.ctrl, q.end);
+                    }
+                }
+                Curve::Quad(q) => {
+                    painter.quad_to(q.ctrl, q.end);
+                }
+                Curve::Arc(_) => {
+                    for q in curve.to_quads(tolerance) {
+                        painter.quad_to(q.ctrl, q.end);
+                    }
+                }
+            }
+        }
+
+        if self.close {
+            painter.close();
+        }
+
+        painter.finish()
+    }
+
+    // Check if a candidate whose bounding box is `distance` away from target can be skipped,
+    // because it can't be nearer then current best one.
+    fn prunable(distance: f64, best: &Option<Nearest<P>>) -> bool {
+        best.as_ref()
+            .is_some_and(|b| distance > b.distance + PRUNE_EPSILON)
+    }
+
+    fn nearest_endpoints_iter<'out, 'a: 'out, 'b: 'out>(
+        &'a self, target: &'b P,
+    ) -> impl Iterator<Item = Nearest<P>> + 'out {
+        self.points
+            .iter()
+            .enumerate()
+            .map(|(i, p)| Nearest::new_from_point(p.point(), target).with_index(i))
+    }
+
+    pub fn nearest_endpoint(&self, target: &P) -> Option<Nearest<P>> {
+        self.nearest_endpoints_iter(target).min()
+    }
+
+    /// Find nearest point on curves to the target.
+    ///
+    /// Curves are checked from near to far by distance of their control point bounding box, and
+    /// stops when the box is farther then the nearest point already found.
+    pub fn nearest_point_on_curves(&self, target: &P, allow_endpoint: bool) -> Option<Nearest<P>> {
+        let mut curves: Vec<_> = self
+            .curves()
+            .enumerate()
+            .map(|(i, c)| (c.control_bounds().distance_to(target), i, c))
+            .collect();
+        curves.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut best = if allow_endpoint {
+            self.nearest_endpoint(target)
+        } else {
+            None
+        };
+
+        for (d, i, curve) in curves {
+            if Self::prunable(d, &best) {
+                break;
+            }
+
+            if let Some(n) = curve.nearest_to(target, false) {
+                let n = n.with_index(i);
+                if best.as_ref().is_none_or(|b| n < *b) {
+                    best.replace(n);
+                }
+            }
+        }
+
+        best
+    }
+}
+
+impl<P> FromIterator<CurvePoint<P>> for Shape<P> {
+    fn from_iter<T: IntoIterator<Item = CurvePoint<P>>>(iter: T) -> Self {
+        Self {
+            points: iter.into_iter().collect(),
+            close: true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // the origin brute force method
+    fn nearest_brute_force(
+        shape: &Shape<(f64, f64)>, target: &(f64, f64), allow_endpoint: bool,
+    ) -> Option<Nearest<(f64, f64)>> {
+        let p = shape
+            .curves()
+            .enumerate()
+            .flat_map(|(i, s)| s.nearest_to(target, false).map(|p| p.with_index(i)));
+
+        let endpoints = shape
+            .points()
+            .iter()
+            .enumerate()
+            .map(|(i, p)| Nearest::new_from_point(p.point(), target).with_index(i));
+
+        if allow_endpoint {
+            p.chain(endpoints).min()
+        } else {
+            p.min()
+        }
+    }
+
+    #[test]
+    fn shape_nearest_pruned_same_as_brute_force() {
+        // simple LCG, make test deterministic
+        let mut seed = 42_u64;
+        let mut rand = move || {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+            (seed >> 11) as f64 / (1_u64 << 53) as f64 * 100.0 - 50.0
+        };
+
+        let shape: Shape<(f64, f64)> = (0..30)
+            .map(|i| {
+                let p = CornerPoint::new((rand(), rand()));
+                if i % 3 == 0 {
+                    p.into()
+                } else {
+                    p.with_in_ctrl((rand(), rand()))
+                        .with_out_ctrl((rand(), rand()))
+                        .into()
+                }
+            })
+            .collect();
+
+        for _ in 0..20 {
+            let target = (rand(), rand());
+            for allow_endpoint in [false, true] {
+                let a = shape
+                    .nearest_point_on_curves(&target, allow_endpoint)
+                    .unwrap();
+                let b = nearest_brute_force(&shape, &target, allow_endpoint).unwrap();
+                assert_eq!((a.index, a.t, a.point), (b.index, b.t, b.point));
+            }
+        }
+    }
+
+    #[test]
+    fn shape_to_quads() {
+        let mut painter = ShapePainter::new();
+        painter
+            .move_to((0.0, 0.0))
+            .line_to((10.0, 0.0))
+            .cubic_to((20.0, 0.0), (20.0, 10.0), (10.0, 10.0))
+            .close();
+        let shape = painter.finish();
+
+        let quads = shape.to_quads(0.01);
+        assert!(quads.closed());
+        assert!(quads.len() > shape.len());
+
+        let curves: Vec<_> = quads.curves().collect();
+        assert!(matches!(curves[0], Curve::Segment(_)));
+        assert!(matches!(curves.last().unwrap(), Curve::Segment(_)));
+        assert!(curves[1..curves.len() - 1]
+            .iter()
+            .all(|c| matches!(c, Curve::Quad(_))));
+    }
+
+    #[test]
+    fn shape_remove_on_curve() {
+        let mut painter = ShapePainter::new();
+        painter
+            .move_to((0.0, 0.0))
+            .cubic_to((0.0, 10.0), (30.0, 10.0), (30.0, 0.0))
+            .line_to((40.0, 0.0))
+            .line_to((50.0, 5.0));
+        let origin = painter.finish();
+
+        // inverse of insert_on_curve
+        let mut shape = origin.clone();
+        shape.insert_on_curve(0, 0.3);
+        assert_eq!(shape.len(), 5);
+        let error = shape.remove_on_curve(1).unwrap();
+        assert!(error < 1e-6, "{error}");
+        assert_eq!(shape.len(), 4);
+        let (Curve::Bezier(a), Curve::Bezier(b)) = (
+            origin.curves().next().unwrap(),
+            shape.curves().next().unwrap(),
+        ) else {
+            panic!()
+        };
+        assert!(a.ctrl1.distance(&b.ctrl1) < 1e-4);
+        assert!(a.ctrl2.distance(&b.ctrl2) < 1e-4);
+
+        // two segments become one
+        let mut shape = origin.clone();
+        let error = shape.remove_on_curve(2).unwrap();
+        assert!((error - 50.0 / libm::sqrt(425.0)).abs() < 1e-12);
+        assert!(matches!(shape.curves().nth(1), Some(Curve::Segment(_))));
+
+        // endpoints of open shape are kept
+        assert!(shape.remove_on_curve(0).is_none());
+        assert!(shape.remove_on_curve(2).is_none());
+    }
+
+    #[test]
+    fn shape_insert_remove_on_arc() {
+        let mut painter = ShapePainter::new();
+        painter
+            .move_to((10.0, 0.0))
+            .arc_to((10.0, 5.0), 0.0, true, true, (0.0, -5.0))
+            .line_to((10.0, -5.0));
+        let origin = painter.finish();
+        let Some(Curve::Arc(arc)) = origin.curves().next() else {
+            panic!()
+        };
+
+        // both parts are arcs on the same ellipse
+        let mut shape = origin.clone();
+        shape.insert_on_curve(0, 0.75);
+        assert_eq!(shape.len(), 4);
+        assert_eq!(shape.points()[1].point(), &arc.at(0.75));
+        let curves: Vec<_> = shape.curves().collect();
+        for (c, t) in curves[..2].iter().zip([0.375, 0.875]) {
+            let Curve::Arc(a) = c else { panic!() };
+            assert!(a.center.distance(&arc.center) < 1e-9);
+            assert!(a.at(0.5).distance(&arc.at(t)) < 1e-9);
+        }
+
+        // and merged back into one
+        let error = shape.remove_on_curve(1).unwrap();
+        assert!(error < 1e-9, "{error}");
+        assert_eq!(shape.len(), 3);
+        let Some(Curve::Arc(merged)) = shape.curves().next() else {
+            panic!()
+        };
+        assert!((merged.sweep_angle - arc.sweep_angle).abs() < 1e-9);
+        assert!(merged.at(0.3).distance(&arc.at(0.3)) < 1e-9);
+
+        // bounds include the bulge of arc
+        let bounds = origin.control_bounds().unwrap();
+        assert!((bounds.height() - 10.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,452 @@
+use crate::{Arc, Curve, Point2D, Poly, Shape};
+
+/// Direction in which a closed contour goes around its inside.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    Clockwise,
+    CounterClockwise,
+}
+
+/// Second moments of area, also known as area moments of inertia.
+///
+/// Like the area, values are signed by the orientation of the shape, they are positive for a
+/// counterclockwise shape.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SecondMoments {
+    /// Integral of y^2 over the area, moment about the x axis.
+    pub xx: f64,
+    /// Integral of x^2 over the area, moment about the y axis.
+    pub yy: f64,
+    /// Integral of xy over the area, the product moment.
+    pub xy: f64,
+}
+
+// Function of the curve parameter, which is closed under multiplication and differentiation, and
+// can be integrated exactly over the parameter range of the curve.
+trait Integrand: Sized {
+    fn mul(&self, rhs: &Self) -> Self;
+    fn sub(&self, rhs: &Self) -> Self;
+    fn derivative(&self) -> Self;
+    fn integrate(&self) -> f64;
+}
+
+// Polynomial curves have t in [0, 1].
+impl Integrand for Poly {
+    fn mul(&self, rhs: &Self) -> Self {
+        Poly::mul(self, rhs)
+    }
+
+    fn sub(&self, rhs: &Self) -> Self {
+        Poly::sub(self, rhs)
+    }
+
+    fn derivative(&self) -> Self {
+        Poly::derivative(self)
+    }
+
+    fn integrate(&self) -> f64 {
+        // the constant term of antiderivative is zero
+        self.integral().eval(1.0)
+    }
+}
+
+// Max degree of trigonometric polynomials, integrands of the second moments are quartic.
+const TRIG_DEGREE: usize = 4;
+
+// cos^i θ sin^j θ
+fn cos_sin(theta: f64, i: usize, j: usize) -> f64 {
+    let (sin, cos) = libm::sincos(theta);
+    (0..i).fold(1.0, |v, _| v * cos) * (0..j).fold(1.0, |v, _| v * sin)
+}
+
+// ∫ cos^i θ sin^j θ dθ over [a, b], by the reduction formulas.
+fn cos_sin_integral(i: usize, j: usize, a: f64, b: f64) -> f64 {
+    let n = (i + j) as f64;
+    let diff = |f: &dyn Fn(f64) -> f64| f(b) - f(a);
+
+    match (i, j) {
+        (0, 0) => b - a,
+        (1, 0) => diff(&libm::sin),
+        (0, 1) => -diff(&libm::cos),
+        (1, 1) => diff(&|t| cos_sin(t, 0, 2) / 2.0),
+        (i, j) if i >= 2 => {
+            diff(&|t| cos_sin(t, i - 1, j + 1)) / n
+                + (i - 1) as f64 / n * cos_sin_integral(i - 2, j, a, b)
+        }
+        (i, j) => {
+            -diff(&|t| cos_sin(t, i + 1, j - 1)) / n
+                + (j - 1) as f64 / n * cos_sin_integral(i, j - 2, a, b)
+        }
+    }
+}
+
+// Polynomial of cos θ and sin θ with θ in [start, end], coefficient [i][j] is of cos^i θ sin^j θ.
+#[derive(Clone)]
+struct TrigPoly {
+    coefficients: [[f64; TRIG_DEGREE + 1]; TRIG_DEGREE + 1],
+    start: f64,
+    end: f64,
+}
+
+impl TrigPoly {
+    // c + a cos θ + b sin θ
+    fn linear(c: f64, a: f64, b: f64, start: f64, end: f64) -> Self {
+        let mut coefficients = [[0.0; TRIG_DEGREE + 1]; TRIG_DEGREE + 1];
+        coefficients[0][0] = c;
+        coefficients[1][0] = a;
+        coefficients[0][1] = b;
+
+        Self {
+            coefficients,
+            start,
+            end,
+        }
+    }
+
+    fn terms(&self) -> impl Iterator<Item = (usize, usize, f64)> + '_ {
+        self.coefficients.iter().enumerate().flat_map(|(i, row)| {
+            row.iter()
+                .enumerate()
+                .filter(|(_, c)| **c != 0.0)
+                .map(move |(j, c)| (i, j, *c))
+        })
+    }
+
+    fn with_coefficients(&self, coefficients: [[f64; TRIG_DEGREE + 1]; TRIG_DEGREE + 1]) -> Self {
+        Self {
+            coefficients,
+            start: self.start,
+            end: self.end,
+        }
+    }
+}
+
+impl Integrand for TrigPoly {
+    fn mul(&self, rhs: &Self) -> Self {
+        let mut coefficients = [[0.0; TRIG_DEGREE + 1]; TRIG_DEGREE + 1];
+        for (i1, j1, c1) in self.terms() {
+            for (i2, j2, c2) in rhs.terms() {
+                coefficients[i1 + i2][j1 + j2] += c1 * c2;
+            }
+        }
+
+        self.with_coefficients(coefficients)
+    }
+
+    fn sub(&self, rhs: &Self) -> Self {
+        let mut coefficients = self.coefficients;
+        for (i, j, c) in rhs.terms() {
+            coefficients[i][j] -= c;
+        }
+
+        self.with_coefficients(coefficients)
+    }
+
+    // (cos^i sin^j)' = -i cos^(i-1) sin^(j+1) + j cos^(i+1) sin^(j-1)
+    fn derivative(&self) -> Self {
+        let mut coefficients = [[0.0; TRIG_DEGREE + 1]; TRIG_DEGREE + 1];
+        for (i, j, c) in self.terms() {
+            if i > 0 {
+                coefficients[i - 1][j + 1] -= i as f64 * c;
+            }
+            if j > 0 {
+                coefficients[i + 1][j - 1] += j as f64 * c;
+            }
+        }
+
+        self.with_coefficients(coefficients)
+    }
+
+    fn integrate(&self) -> f64 {
+        self.terms()
+            .map(|(i, j, c)| c * cos_sin_integral(i, j, self.start, self.end))
+            .sum()
+    }
+}
+
+// Integrals over the area enclosed by a contour, each converted to a line integral along the
+// contour by Green's theorem.
+#[derive(Default)]
+struct AreaIntegrals {
+    // ∫∫ 1 dA = ∮ (x dy - y dx) / 2
+    area: f64,
+    // ∫∫ x dA = ∮ x^2 dy / 2
+    x: f64,
+    // ∫∫ y dA = -∮ y^2 dx / 2
+    y: f64,
+    // ∫∫ y^2 dA = -∮ y^3 dx / 3
+    xx: f64,
+    // ∫∫ x^2 dA = ∮ x^3 dy / 3
+    yy: f64,
+    // ∫∫ xy dA = ∮ x^2 y dy / 2
+    xy: f64,
+}
+
+impl AreaIntegrals {
+    // Accumulate line integrals along a curve (x(t), y(t)), polynomial or trigonometric.
+    fn add_curve<F: Integrand>(&mut self, x: &F, y: &F) {
+        let dx = x.derivative();
+        let dy = y.derivative();
+        let x2 = x.mul(x);
+        let y2 = y.mul(y);
+
+        let integrate = |p: F| p.integrate();
+
+        self.area += integrate(x.mul(&dy).sub(&y.mul(&dx))) / 2.0;
+        self.x += integrate(x2.mul(&dy)) / 2.0;
+        self.y -= integrate(y2.mul(&dx)) / 2.0;
+        self.xx -= integrate(y2.mul(y).mul(&dx)) / 3.0;
+        self.yy += integrate(x2.mul(x).mul(&dy)) / 3.0;
+        self.xy += integrate(x2.mul(y).mul(&dy)) / 2.0;
+    }
+
+    // Integrals of a contour, with coordinates relative to `origin`.
+    //
+    // Coordinates are moved near the origin to avoid cancellation of large terms, use
+    // `translate` to get the integrals in the original coordinates.
+    fn of_shape<P: Point2D>(shape: &Shape<P>, origin: &P) -> Self {
+        let mut integrals = Self::default();
+
+        let polys = |coefficients: &[P]| -> (Poly, Poly) {
+            let last = coefficients.len() - 1;
+            (
+                coefficients
+                    .iter()
+                    .enumerate()
+                    .map(|(i, p)| if i == last { p.x() - origin.x() } else { p.x() })
+                    .collect(),
+                coefficients
+                    .iter()
+                    .enumerate()
+                    .map(|(i, p)| if i == last { p.y() - origin.y() } else { p.y() })
+                    .collect(),
+            )
+        };
+
+        let segment = |start: &P, end: &P| polys(&[end.minus(start), start.clone()]);
+
+        for curve in shape.curves() {
+            let (x, y) = match curve {
+                Curve::Segment(s) => segment(s.start(), s.end()),
+                Curve::Quad(q) => polys(&q.parametric_function_coefficients()),
+                Curve::Bezier(b) => polys(&b.parametric_function_coefficients()),
+                Curve::Arc(a) => {
+                    let (x, y) = Self::arc_trig_polys(&a, origin);
+                    integrals.add_curve(&x, &y);
+                    continue;
+                }
+            };
+            integrals.add_curve(&x, &y);
+        }
+
+        // open shape is closed by a line segment, the same as filling
+        let points = shape.points();
+        if !shape.closed() && points.len() >= 2 {
+            let (x, y) = segment(points[points.len() - 1].point(), points[0].point());
+            integrals.add_curve(&x, &y);
+        }
+
+        integrals
+    }
+
+    // Coordinates of an arc as functions of its angle θ, relative to `origin`.
+    fn arc_trig_polys<P: Point2D>(a: &Arc<P>, origin: &P) -> (TrigPoly, TrigPoly) {
+        let (sin, cos) = libm::sincos(a.rotation.to_radians());
+        let (rx, ry) = (a.radii.x(), a.radii.y());
+        let c = a.center.minus(origin);
+        let start = a.start_angle.to_radians();
+        let end = (a.start_angle + a.sweep_angle).to_radians();
+
+        (
+            TrigPoly::linear(c.x(), rx * cos, -ry * sin, start, end),
+            TrigPoly::linear(c.y(), rx * sin, ry * cos, start, end),
+        )
+    }
+
+    // Integrals after moving the area by (ox, oy), by the parallel axis theorem.
+    fn translate(&self, ox: f64, oy: f64) -> Self {
+        Self {
+            area: self.area,
+            x: self.x + ox * self.area,
+            y: self.y + oy * self.area,
+            xx: self.xx + 2.0 * oy * self.y + oy * oy * self.area,
+            yy: self.yy + 2.0 * ox * self.x + ox * ox * self.area,
+            xy: self.xy + ox * self.y + oy * self.x + ox * oy * self.area,
+        }
+    }
+
+    fn centroid(&self) -> Option<(f64, f64)> {
+        (self.area != 0.0).then(|| (self.x / self.area, self.y / self.area))
+    }
+}
+
+impl<P: Point2D> Shape<P> {
+    // Integrals in the original coordinates, relative to the first point when computing.
+    fn area_integrals(&self) -> AreaIntegrals {
+        let Some(first) = self.points().first() else {
+            return AreaIntegrals::default();
+        };
+        let origin = first.point();
+
+        AreaIntegrals::of_shape(self, origin).translate(origin.x(), origin.y())
+    }
+
+    /// Signed area enclosed by the shape, positive if it is counterclockwise in a y-up
+    /// coordinate system.
+    ///
+    /// It is computed in closed form from control points, an open shape is treated as closed by
+    /// a line segment from its last point to the first one. Parts of a self intersecting shape
+    /// are counted by their winding numbers.
+    pub fn area(&self) -> f64 {
+        self.area_integrals().area
+    }
+
+    /// Orientation of the shape by the sign of its area, `None` if the area is zero.
+    ///
+    /// In a y-down coordinate system, such as SVG, the orientation appears flipped.
+    pub fn orientation(&self) -> Option<Orientation> {
+        let area = self.area();
+
+        if area > 0.0 {
+            Some(Orientation::CounterClockwise)
+        } else if area < 0.0 {
+            Some(Orientation::Clockwise)
+        } else {
+            None
+        }
+    }
+
+    /// Centroid of the enclosed area, `None` if the area is zero.
+    pub fn centroid(&self) -> Option<P> {
+        let (x, y) = self.area_integrals().centroid()?;
+
+        Some(P::from_xy(x, y))
+    }
+
+    /// Second moments of area about the x and y axes through the origin.
+    pub fn second_moments(&self) -> SecondMoments {
+        let integrals = self.area_integrals();
+
+        SecondMoments {
+            xx: integrals.xx,
+            yy: integrals.yy,
+            xy: integrals.xy,
+        }
+    }
+
+    /// Second moments of area about the axes through the centroid, `None` if the area is zero.
+    pub fn central_second_moments(&self) -> Option<SecondMoments> {
+        let origin = self.points().first()?.point();
+
+        let integrals = AreaIntegrals::of_shape(self, origin);
+        let (cx, cy) = integrals.centroid()?;
+        let central = integrals.translate(-cx, -cy);
+
+        Some(SecondMoments {
+            xx: central.xx,
+            yy: central.yy,
+            xy: central.xy,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::f64::consts::PI;
+
+    use super::*;
+    use crate::ShapePainter;
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() <= 1e-9 * b.abs().max(1.0), "{a} != {b}");
+    }
+
+    #[test]
+    fn moment_rectangle() {
+        // 4 x 2 rectangle at (1, 1), counterclockwise
+        let shape = Shape::<(f64, f64)>::parse_svg_path("M1 1 H5 V3 H1 Z").unwrap();
+
+        assert_eq!(shape.area(), 8.0);
+        assert_eq!(shape.orientation(), Some(Orientation::CounterClockwise));
+        assert_eq!(shape.centroid(), Some((3.0, 2.0)));
+
+        // bh^3/12 and hb^3/12 about the centroid
+        let central = shape.central_second_moments().unwrap();
+        assert_close(central.xx, 4.0 * 8.0 / 12.0);
+        assert_close(central.yy, 2.0 * 64.0 / 12.0);
+        assert_close(central.xy, 0.0);
+
+        let moments = shape.second_moments();
+        assert_close(moments.xx, central.xx + 8.0 * 4.0);
+        assert_close(moments.xy, 8.0 * 3.0 * 2.0);
+
+        // reversed, and open shape is closed implicitly
+        let shape = Shape::<(f64, f64)>::parse_svg_path("M1 1 V3 H5 V1").unwrap();
+        assert_eq!(shape.area(), -8.0);
+        assert_eq!(shape.orientation(), Some(Orientation::Clockwise));
+        assert_eq!(shape.centroid(), Some((3.0, 2.0)));
+    }
+
+    #[test]
+    fn moment_curves() {
+        // parabola y = 1 - x^2 over [-1, 1] with its chord, area is 4/3, centroid y is 2/5
+        let shape = Shape::<(f64, f64)>::parse_svg_path("M1 0 Q0 2 -1 0 Z").unwrap();
+        assert_close(shape.area(), 4.0 / 3.0);
+        let (x, y) = shape.centroid().unwrap();
+        assert_close(x, 0.0);
+        assert_close(y, 0.4);
+
+        // far from origin, unit circle approximated by cubic curves
+        let (cx, cy) = (1e6, -2e6);
+        let k = 0.551_915_024_494;
+        let mut painter = ShapePainter::new();
+        painter
+            .move_to((cx + 1.0, cy))
+            .cubic_to((cx + 1.0, cy + k), (cx + k, cy + 1.0), (cx, cy + 1.0))
+            .cubic_to((cx - k, cy + 1.0), (cx - 1.0, cy + k), (cx - 1.0, cy))
+            .cubic_to((cx - 1.0, cy - k), (cx - k, cy - 1.0), (cx, cy - 1.0))
+            .cubic_to((cx + k, cy - 1.0), (cx + 1.0, cy - k), (cx + 1.0, cy))
+            .close();
+        let circle = painter.finish();
+
+        assert!((circle.area() - PI).abs() < 1e-3);
+        let (x, y) = circle.centroid().unwrap();
+        assert!((x - cx).abs() < 1e-9 && (y - cy).abs() < 1e-9);
+        let central = circle.central_second_moments().unwrap();
+        assert!((central.xx - PI / 4.0).abs() < 1e-3);
+        assert!((central.yy - central.xx).abs() < 1e-9);
+        assert!(central.xy.abs() < 1e-9);
+    }
+
+    #[test]
+    fn moment_arcs() {
+        // rotated ellipse far from origin, made of three arcs, moments are exact
+        let (cx, cy) = (1e3, -2e3);
+        let (a, b) = (3.0, 2.0);
+        let ellipse = Arc::new((cx, cy), (a, b), 30.0, 10.0, 360.0);
+        let mut painter = ShapePainter::new();
+        painter.move_to(ellipse.start());
+        for t in [1.0 / 3.0, 2.0 / 3.0, 0.0] {
+            painter.arc_to((a, b), 30.0, false, true, ellipse.at(t));
+        }
+        painter.close();
+        let ellipse = painter.finish();
+
+        assert_close(ellipse.area(), PI * a * b);
+        let (x, y) = ellipse.centroid().unwrap();
+        assert!((x - cx).abs() < 1e-9 && (y - cy).abs() < 1e-9);
+
+        // πab(a²cos²φ + b²sin²φ)/4 and so on, rotated by φ
+        let (sin, cos) = libm::sincos(30.0_f64.to_radians());
+        let (ia, ib) = (PI * a * a * a * b / 4.0, PI * a * b * b * b / 4.0);
+        let central = ellipse.central_second_moments().unwrap();
+        assert_close(central.yy, ia * cos * cos + ib * sin * sin);
+        assert_close(central.xx, ia * sin * sin + ib * cos * cos);
+        assert_close(central.xy, (ia - ib) * sin * cos);
+
+        // half disk by an arc and its diameter, centroid is 4r/3π above the diameter
+        let shape = Shape::<(f64, f64)>::parse_svg_path("M1 0 A1 1 0 0 1 -1 0 Z").unwrap();
+        assert_close(shape.area(), PI / 2.0);
+        assert_close(shape.centroid().unwrap().1, 4.0 / (3.0 * PI));
+    }
+}
@@ -0,0 +1,299 @@
+use alloc::vec::Vec;
+
+use crate::{ArcParams, CornerPoint, Curve, CurvePoint, Point2D, Shape};
+
+// Distance under which the last point of a closed path is treated as the same as the first.
+const CLOSE_EPSILON: f64 = 1e-9;
+
+/// Construct a [Shape] curve by curve, instead of point by point.
+///
+/// Control points of each curve are filled into the in/out ctrl of its endpoints. When finished,
+/// points whose two ctrl points are collinear with it become [SmoothPoint], others become
+/// [CornerPoint].
+///
+/// ```
+/// use bezier::{CurvePoint, ShapePainter};
+///
+/// let mut painter = ShapePainter::new();
+/// painter
+///     .move_to((0.0, 0.0))
+///     .line_to((10.0, 0.0))
+///     .cubic_to((20.0, 0.0), (20.0, 10.0), (10.0, 10.0))
+///     .close();
+/// let shape = painter.finish();
+///
+/// assert_eq!(shape.len(), 3);
+/// assert!(matches!(shape.points()[1], CurvePoint::Corner(_)));
+/// assert!(shape.closed());
+/// ```
+///
+/// [SmoothPoint]: crate::SmoothPoint
+pub struct ShapePainter<P> {
+    points: Vec<CornerPoint<P>>,
+    closed: bool,
+}
+
+impl<P> Default for ShapePainter<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P> ShapePainter<P> {
+    pub fn new() -> Self {
+        Self {
+            points: Vec::new(),
+            closed: false,
+        }
+    }
+
+    /// Count of points painted.
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn closed(&self) -> bool {
+        self.closed
+    }
+
+    /// Current pen position, which is the start point of next curve.
+    pub fn current(&self) -> Option<&P> {
+        if self.closed {
+            self.points.first()
+        } else {
+            self.points.last()
+        }
+        .map(|p| p.point())
+    }
+
+    fn last_mut(&mut self) -> &mut CornerPoint<P> {
+        assert!(!self.closed, "can't paint on a closed shape");
+        self.points
+            .last_mut()
+            .expect("move_to must be called before painting curves")
+    }
+}
+
+impl<P: Point2D> ShapePainter<P> {
+    /// Set start point of the shape.
+    ///
+    /// # Panics
+    ///
+    /// When any curve has been painted, because [Shape] only has one contour.
+    pub fn move_to(&mut self, p: P) -> &mut Self {
+        assert!(
+            !self.closed && self.points.len() <= 1,
+            "shape can only have one contour"
+        );
+
+        self.points.clear();
+        self.points.push(CornerPoint::new(p));
+        self
+    }
+
+    /// Paint a line [Segment](crate::Segment) from current point to `p`.
+    ///
+    /// # Panics
+    ///
+    /// When no start point or shape is closed.
+    pub fn line_to(&mut self, p: P) -> &mut Self {
+        self.last_mut();
+        self.points.push(CornerPoint::new(p));
+        self
+    }
+
+    /// Paint a quadratic bezier curve from current point to `p`.
+    ///
+    /// The only control point is set as out ctrl of current point.
+    ///
+    /// # Panics
+    ///
+    /// When no start point or shape is closed.
+    pub fn quad_to(&mut self, ctrl: P, p: P) -> &mut Self {
+        self.last_mut().update_out_ctrl(ctrl);
+        self.points.push(CornerPoint::new(p));
+        self
+    }
+
+    /// Paint a cubic bezier curve from current point to `p`.
+    ///
+    /// # Panics
+    ///
+    /// When no start point or shape is closed.
+    pub fn cubic_to(&mut self, ctrl1: P, ctrl2: P, p: P) -> &mut Self {
+        self.last_mut().update_out_ctrl(ctrl1);
+        self.points.push(CornerPoint::new(p).with_in_ctrl(ctrl2));
+        self
+    }
+
+    /// Paint an elliptical arc from current point to `p`, with the arguments of SVG `A` command.
+    ///
+    /// The arc is kept exactly as the out arc of current point, see [ArcParams].
+    ///
+    /// # Panics
+    ///
+    /// When no start point or shape is closed.
+    pub fn arc_to(
+        &mut self, radii: P, rotation: f64, large_arc: bool, sweep: bool, p: P,
+    ) -> &mut Self {
+        self.last_mut()
+            .update_out_arc(ArcParams::new(radii, rotation, large_arc, sweep));
+        self.points.push(CornerPoint::new(p));
+        self
+    }
+
+    // Paint a curve from current point, the start point of curve is ignored.
+    pub(crate) fn curve_to(&mut self, curve: &Curve<P>) -> &mut Self {
+        match curve {
+            Curve::Segment(l) => self.line_to(l.end().clone()),
+            Curve::Quad(q) => self.quad_to(q.ctrl.clone(), q.end.clone()),
+            Curve::Bezier(b) => self.cubic_to(b.ctrl1.clone(), b.ctrl2.clone(), b.end.clone()),
+            Curve::Arc(a) => {
+                // resolving an arc from endpoints is ill-conditioned near 180 degrees, and
+                // impossible at 360 degrees, so it's split into parts of at most 90 degrees
+                let n = libm::ceil(libm::fabs(a.sweep_angle) / 90.0).max(1.0) as usize;
+                let mut rest = a.clone();
+                for i in (1..=n).rev() {
+                    let (part, right) = rest.split_at(1.0 / i as f64);
+                    let ArcParams {
+                        radii,
+                        rotation,
+                        large_arc,
+                        sweep,
+                    } = part.params();
+                    self.arc_to(radii, rotation, large_arc, sweep, part.end());
+                    rest = right;
+                }
+                self
+            }
+        }
+    }
+
+    /// Close the shape.
+    ///
+    /// If current point is the same as start point, they are merged into one point, so the last
+    /// curve painted becomes the closing curve. Otherwise a line segment is used to close it.
+    pub fn close(&mut self) -> &mut Self {
+        if self.closed {
+            return self;
+        }
+
+        self.closed = true;
+
+        if self.points.len() >= 2 {
+            let first = self.points.first().unwrap().point();
+            let last = self.points.last().unwrap();
+            if last.point().distance(first) <= CLOSE_EPSILON {
+                let last = self.points.pop().unwrap();
+                if let Some(ctrl) = last.in_ctrl() {
+                    self.points[0].update_in_ctrl(ctrl.clone());
+                }
+            }
+        }
+
+        self
+    }
+
+    pub fn finish(self) -> Shape<P> {
+        let mut shape: Shape<P> = self
+            .points
+            .into_iter()
+            .map(|p| {
+                // point with out arc is never smooth
+                if p.has_out_arc() {
+                    return p.into();
+                }
+
+                CurvePoint::from_ctrls(
+                    p.point().clone(),
+                    p.in_ctrl().cloned(),
+                    p.out_ctrl().cloned(),
+                )
+            })
+            .collect();
+
+        shape.set_close(self.closed);
+
+        shape
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Arc, Curve};
+
+    #[test]
+    fn painter_smooth_point() {
+        let mut painter = ShapePainter::new();
+        painter
+            .move_to((0.0, 0.0))
+            .cubic_to((0.0, 10.0), (10.0, 10.0), (10.0, 0.0))
+            .cubic_to((10.0, -10.0), (20.0, -10.0), (20.0, 0.0));
+        let shape = painter.finish();
+
+        assert!(!shape.closed());
+        assert!(matches!(shape.points()[0], CurvePoint::Corner(_)));
+        assert!(matches!(shape.points()[1], CurvePoint::Smooth(_)));
+        assert!(matches!(shape.points()[2], CurvePoint::Corner(_)));
+    }
+
+    #[test]
+    fn painter_close_merge() {
+        let mut painter = ShapePainter::new();
+        painter
+            .move_to((0.0, 0.0))
+            .line_to((10.0, 0.0))
+            .quad_to((10.0, 10.0), (0.0, 0.0))
+            .close();
+        assert_eq!(painter.current(), Some(&(0.0, 0.0)));
+        let shape = painter.finish();
+
+        assert_eq!(shape.len(), 2);
+        let curves: Vec<_> = shape.curves().collect();
+        assert!(matches!(curves[0], Curve::Segment(_)));
+        assert!(matches!(curves[1], Curve::Quad(_)));
+    }
+
+    #[test]
+    fn painter_arc() {
+        let mut painter = ShapePainter::new();
+        painter
+            .move_to((10.0, 0.0))
+            .arc_to((10.0, 10.0), 0.0, false, true, (0.0, 10.0));
+        let shape = painter.finish();
+        assert!(matches!(shape.points()[0], CurvePoint::Corner(_)));
+        let Some(Curve::Arc(arc)) = shape.curves().next() else {
+            panic!()
+        };
+        assert!(arc.center.distance(&(0.0, 0.0)) < 1e-12);
+
+        // full circle can't be resolved from its endpoints, it's split into quarters
+        let circle = Arc::new((0.0, 0.0), (10.0, 10.0), 0.0, 0.0, 360.0);
+        let mut painter = ShapePainter::new();
+        painter
+            .move_to(circle.start())
+            .curve_to(&Curve::Arc(circle));
+        let shape = painter.finish();
+        assert_eq!(shape.len(), 5);
+        for c in shape.curves() {
+            let Curve::Arc(a) = c else { panic!() };
+            assert!(a.center.distance(&(0.0, 0.0)) < 1e-9);
+            assert!((a.sweep_angle - 90.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn painter_multiple_contour() {
+        let mut painter = ShapePainter::new();
+        painter
+            .move_to((0.0, 0.0))
+            .line_to((1.0, 0.0))
+            .move_to((2.0, 2.0));
+    }
+}
@@ -0,0 +1,397 @@
+use alloc::vec::Vec;
+use core::f64::consts::{FRAC_PI_2, TAU};
+
+use super::ShapePainter;
+use crate::{Arc, Bezier, Curve, Point2D, QuadBezier, Segment, Shape};
+
+// Tolerance of offset curves, relative to half of the stroke width.
+const STROKE_TOLERANCE: f64 = 1e-3;
+
+// Relative distance under which two offset points are treated as connected.
+const CONNECT_EPSILON: f64 = 1e-9;
+
+/// Shape used at the corner where two curves of a stroke meet, see [Shape::stroke].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineJoin {
+    /// Extend the outer edges until they meet, fall back to bevel if the miter is too long.
+    Miter,
+    /// Circular arc around the corner.
+    Round,
+    /// Straight line between the outer edges.
+    Bevel,
+}
+
+/// Shape used at the ends of an open stroke, see [Shape::stroke].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineCap {
+    /// Stroke ends exactly at the endpoint.
+    Butt,
+    /// Half circle around the endpoint.
+    Round,
+    /// Stroke extends half width beyond the endpoint.
+    Square,
+}
+
+pub(super) fn start_of<P: Point2D>(c: &Curve<P>) -> P {
+    c.at(0.0)
+}
+
+pub(super) fn end_of<P: Point2D>(c: &Curve<P>) -> P {
+    c.at(1.0)
+}
+
+pub(super) fn reversed<P: Point2D>(c: &Curve<P>) -> Curve<P> {
+    match c {
+        Curve::Bezier(b) => Curve::Bezier(Bezier::new(
+            b.end.clone(),
+            b.ctrl2.clone(),
+            b.ctrl1.clone(),
+            b.start.clone(),
+        )),
+        Curve::Quad(q) => Curve::Quad(QuadBezier::new(
+            q.end.clone(),
+            q.ctrl.clone(),
+            q.start.clone(),
+        )),
+        Curve::Arc(a) => Curve::Arc(Arc::new(
+            a.center.clone(),
+            a.radii.clone(),
+            a.rotation,
+            a.start_angle + a.sweep_angle,
+            -a.sweep_angle,
+        )),
+        Curve::Segment(l) => Curve::Segment(Segment::new(l.end().clone(), l.start().clone())),
+    }
+}
+
+pub(super) fn is_degenerated<P: Point2D>(c: &Curve<P>) -> bool {
+    match c {
+        Curve::Bezier(b) => [&b.ctrl1, &b.ctrl2, &b.end]
+            .into_iter()
+            .all(|p| p.distance(&b.start) == 0.0),
+        Curve::Quad(q) => [&q.ctrl, &q.end]
+            .into_iter()
+            .all(|p| p.distance(&q.start) == 0.0),
+        Curve::Arc(a) => a.sweep_angle == 0.0 || a.radii.length_from_origin() == 0.0,
+        Curve::Segment(l) => l.length() == 0.0,
+    }
+}
+
+fn left_normal<P: Point2D>(t: &P) -> P {
+    P::from_xy(-t.y(), t.x())
+}
+
+// Circular arc around `center` from `from` to `to`, approximated by cubic curves of at most 90
+// degree each.
+fn arc<P: Point2D>(center: &P, from: &P, to: &P, counterclockwise: bool, out: &mut Vec<Curve<P>>) {
+    let radius = from.distance(center);
+    let v0 = from.minus(center);
+    let v1 = to.minus(center);
+    let a0 = libm::atan2(v0.y(), v0.x());
+
+    let mut sweep = libm::atan2(v1.y(), v1.x()) - a0;
+    if counterclockwise && sweep < 0.0 {
+        sweep += TAU;
+    } else if !counterclockwise && sweep > 0.0 {
+        sweep -= TAU;
+    }
+
+    let n = libm::ceil(libm::fabs(sweep) / FRAC_PI_2 - CONNECT_EPSILON).max(1.0) as usize;
+    let step = sweep / n as f64;
+    let k = 4.0 / 3.0 * libm::tan(step / 4.0) * radius;
+
+    let point = |a: f64| {
+        let (sin, cos) = libm::sincos(a);
+        (
+            center.plus(&P::from_xy(cos * radius, sin * radius)),
+            P::from_xy(-sin, cos),
+        )
+    };
+
+    let mut start = from.clone();
+    let (_, mut start_dir) = point(a0);
+    for i in 1..=n {
+        let (mut end, end_dir) = point(a0 + step * i as f64);
+        if i == n {
+            end = to.clone();
+        }
+
+        out.push(Curve::Bezier(Bezier::new(
+            start.clone(),
+            start.plus(&start_dir.scale(k)),
+            end.minus(&end_dir.scale(k)),
+            end.clone(),
+        )));
+
+        start = end;
+        start_dir = end_dir;
+    }
+}
+
+fn line_to<P: Point2D>(out: &mut Vec<Curve<P>>, p: P) {
+    let last = end_of(out.last().unwrap());
+    out.push(Curve::Segment(Segment::new(last, p)));
+}
+
+struct Stroker {
+    half: f64,
+    join: LineJoin,
+    cap: LineCap,
+    miter_limit: f64,
+    tolerance: f64,
+}
+
+impl Stroker {
+    fn connected<P: Point2D>(&self, a: &P, b: &P) -> bool {
+        a.distance(b) <= CONNECT_EPSILON * self.half
+    }
+
+    // Join at corner `p` from the end of `out` to the offset start of the next curve.
+    fn join<P: Point2D>(&self, p: &P, t_in: &P, t_out: &P, out: &mut Vec<Curve<P>>) {
+        let a = end_of(out.last().unwrap());
+        let n_in = left_normal(t_in);
+        let n_out = left_normal(t_out);
+        let b = p.plus(&n_out.scale(self.half));
+
+        if self.connected(&a, &b) {
+            return;
+        }
+
+        // the left side is the outer side only when turning right
+        if t_in.cross(t_out) >= 0.0 {
+            line_to(out, p.clone());
+            line_to(out, b);
+            return;
+        }
+
+        match self.join {
+            LineJoin::Bevel => line_to(out, b),
+            LineJoin::Round => arc(p, &a, &b, false, out),
+            LineJoin::Miter => {
+                let cos = t_in.dot(t_out);
+                let ratio = 1.0 / libm::sqrt((1.0 + cos) / 2.0);
+
+                if ratio.is_finite() && ratio <= self.miter_limit {
+                    let m = p.plus(&n_in.plus(&n_out).scale(self.half / (1.0 + cos)));
+                    line_to(out, m);
+                }
+                line_to(out, b);
+            }
+        }
+    }
+
+    // Cap at end point `p` with tangent `t`, from the end of `out` to the other side.
+    fn cap<P: Point2D>(&self, p: &P, t: &P, out: &mut Vec<Curve<P>>) {
+        let n = left_normal(t).scale(self.half);
+        let a = end_of(out.last().unwrap());
+        let b = p.minus(&n);
+
+        match self.cap {
+            LineCap::Butt => line_to(out, b),
+            LineCap::Round => arc(p, &a, &b, false, out),
+            LineCap::Square => {
+                let ext = t.scale(self.half);
+                line_to(out, a.plus(&ext));
+                line_to(out, b.plus(&ext));
+                line_to(out, b);
+            }
+        }
+    }
+
+    // Offset curves at the left side, connected with joins.
+    fn side<P: Point2D>(&self, curves: &[Curve<P>], closed: bool, out: &mut Vec<Curve<P>>) {
+        let first = out.len();
+
+        for (i, c) in curves.iter().enumerate() {
+            if i > 0 {
+                let prev = &curves[i - 1];
+                self.join(&start_of(c), &prev.tangent(1.0), &c.tangent(0.0), out);
+            }
+
+            for piece in c.offset(self.half, self.tolerance) {
+                // pieces are disconnected at cusps
+                if let Some(last) = out.last().map(end_of) {
+                    if !self.connected(&last, &start_of(&piece)) {
+                        line_to(out, start_of(&piece));
+                    }
+                }
+                out.push(piece);
+            }
+        }
+
+        if closed {
+            let last = curves.last().unwrap();
+            let first_curve = curves.first().unwrap();
+            self.join(
+                &start_of(first_curve),
+                &last.tangent(1.0),
+                &first_curve.tangent(0.0),
+                out,
+            );
+            let start = start_of(&out[first]);
+            if !self.connected(&end_of(out.last().unwrap()), &start) {
+                line_to(out, start);
+            }
+        }
+    }
+}
+
+fn paint<P: Point2D>(curves: &[Curve<P>]) -> Shape<P> {
+    let mut painter = ShapePainter::new();
+    painter.move_to(start_of(&curves[0]));
+
+    for c in curves {
+        painter.curve_to(c);
+    }
+
+    painter.close();
+    painter.finish()
+}
+
+impl<P: Point2D> Shape<P> {
+    /// Convert the stroke of the shape into closed outlines, which covers the stroke area when
+    /// filled with nonzero rule.
+    ///
+    /// Open shape becomes one outline with caps at both ends. Closed shape becomes two outlines
+    /// with opposite orientation and no caps, the first one is at the left side of the shape,
+    /// the second one is at the right side. `miter_limit` is the
+    /// max ratio of miter length to `width`, longer miter joins become bevel.
+    ///
+    /// Curves are offset with tolerance of 1/1000 of the half width. Returns empty if the shape
+    /// has no curve with non-zero length, or `width` is not positive.
+    pub fn stroke(&self, width: f64, join: LineJoin, cap: LineCap, miter_limit: f64) -> Vec<Self> {
+        let curves: Vec<_> = self.curves().filter(|c| !is_degenerated(c)).collect();
+
+        if curves.is_empty() || width <= 0.0 {
+            return Vec::new();
+        }
+
+        let half = width / 2.0;
+        let stroker = Stroker {
+            half,
+            join,
+            cap,
+            miter_limit,
+            tolerance: half * STROKE_TOLERANCE,
+        };
+
+        // the right side is the left side of reversed curves
+        let reversed: Vec<_> = curves.iter().rev().map(reversed).collect();
+
+        let mut left = Vec::new();
+        let mut right = Vec::new();
+
+        if self.closed() {
+            stroker.side(&curves, true, &mut left);
+            stroker.side(&reversed, true, &mut right);
+
+            return vec![paint(&left), paint(&right)];
+        }
+
+        stroker.side(&curves, false, &mut left);
+
+        let last = curves.last().unwrap();
+        stroker.cap(&end_of(last), &last.tangent(1.0), &mut left);
+
+        stroker.side(&reversed, false, &mut right);
+        for c in right {
+            if !stroker.connected(&end_of(left.last().unwrap()), &start_of(&c)) {
+                line_to(&mut left, start_of(&c));
+            }
+            left.push(c);
+        }
+
+        let first = curves.first().unwrap();
+        stroker.cap(&start_of(first), &first.tangent(0.0).negative(), &mut left);
+
+        vec![paint(&left)]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn line() -> Shape<(f64, f64)> {
+        let mut painter = ShapePainter::new();
+        painter.move_to((0.0, 0.0)).line_to((10.0, 0.0));
+        painter.finish()
+    }
+
+    fn square() -> Shape<(f64, f64)> {
+        let mut painter = ShapePainter::new();
+        painter
+            .move_to((0.0, 0.0))
+            .line_to((10.0, 0.0))
+            .line_to((10.0, 10.0))
+            .line_to((0.0, 10.0))
+            .close();
+        painter.finish()
+    }
+
+    fn assert_bounds(shape: &Shape<(f64, f64)>, min: (f64, f64), max: (f64, f64)) {
+        let rect = shape.bounds().unwrap();
+        assert!(rect.min().distance(&min) < 1e-6, "{:?}", rect);
+        assert!(rect.max().distance(&max) < 1e-6, "{:?}", rect);
+    }
+
+    #[test]
+    fn stroke_caps() {
+        let outlines = line().stroke(2.0, LineJoin::Miter, LineCap::Butt, 4.0);
+        assert_eq!(outlines.len(), 1);
+        assert!(outlines[0].closed());
+        assert_eq!(outlines[0].len(), 4);
+        assert_bounds(&outlines[0], (0.0, -1.0), (10.0, 1.0));
+
+        let outlines = line().stroke(2.0, LineJoin::Miter, LineCap::Square, 4.0);
+        assert_bounds(&outlines[0], (-1.0, -1.0), (11.0, 1.0));
+
+        let outlines = line().stroke(2.0, LineJoin::Miter, LineCap::Round, 4.0);
+        assert_bounds(&outlines[0], (-1.0, -1.0), (11.0, 1.0));
+    }
+
+    #[test]
+    fn stroke_joins() {
+        // square is counterclockwise, so the left side is inner
+        let outlines = square().stroke(2.0, LineJoin::Miter, LineCap::Round, 4.0);
+        assert_eq!(outlines.len(), 2);
+        assert_bounds(&outlines[1], (-1.0, -1.0), (11.0, 11.0));
+        assert_eq!(outlines[1].len(), 12);
+
+        // miter of right angle is sqrt(2) times of the width
+        let outlines = square().stroke(2.0, LineJoin::Miter, LineCap::Round, 1.2);
+        assert_eq!(outlines[1].len(), 8);
+
+        let outlines = square().stroke(2.0, LineJoin::Bevel, LineCap::Round, 4.0);
+        assert_eq!(outlines[1].len(), 8);
+        let corner = outlines[1]
+            .nearest_point_on_curves(&(-1.0, -1.0), true)
+            .unwrap();
+        assert!((corner.distance - 2f64.sqrt() / 2.0).abs() < 1e-6);
+
+        let outlines = square().stroke(2.0, LineJoin::Round, LineCap::Round, 4.0);
+        assert_eq!(outlines[1].len(), 8);
+        let corner = outlines[1]
+            .nearest_point_on_curves(&(-1.0, -1.0), true)
+            .unwrap();
+        assert!((corner.distance - (2f64.sqrt() - 1.0)).abs() < 1e-3);
+    }
+
+    #[test]
+    fn stroke_curve_follows_offset() {
+        let mut painter = ShapePainter::new();
+        painter
+            .move_to((0.0, 0.0))
+            .cubic_to((0.0, 100.0), (300.0, 100.0), (300.0, 0.0));
+        let shape = painter.finish();
+
+        let outlines = shape.stroke(10.0, LineJoin::Round, LineCap::Butt, 4.0);
+        assert_eq!(outlines.len(), 1);
+
+        let curve = shape.curves().next().unwrap();
+        for p in outlines[0].flatten(0.01) {
+            let d = curve.nearest_to(&p, true).unwrap().distance;
+            assert!(d <= 5.0 + 0.05, "{d}");
+        }
+    }
+}
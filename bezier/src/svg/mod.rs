@@ -0,0 +1,7 @@
+mod parse;
+mod write;
+
+pub use self::{
+    parse::{SvgPathError, SvgPathErrorKind},
+    write::SvgPathFormat,
+};
@@ -0,0 +1,576 @@
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
+
+use crate::{Arc, Path, Point2D, Shape, ShapePainter};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SvgPathErrorKind {
+    /// Input ends while command arguments are expected.
+    UnexpectedEnd,
+    /// A char which can't start a command or a number.
+    UnexpectedChar(char),
+    /// Malformed number.
+    InvalidNumber,
+    /// Arc flag is not `0` or `1`.
+    InvalidFlag,
+    /// Path data does not start with a moveto command.
+    ExpectedMoveTo,
+    /// The path contains more then one subpath, which can't be represented by a [Shape].
+    MultipleSubpaths,
+}
+
+/// Error when parsing SVG path data, `offset` is the byte offset in input where error occurs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SvgPathError {
+    pub kind: SvgPathErrorKind,
+    pub offset: usize,
+}
+
+impl SvgPathError {
+    fn new(kind: SvgPathErrorKind, offset: usize) -> Self {
+        Self { kind, offset }
+    }
+}
+
+impl Display for SvgPathErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedEnd => f.write_str("unexpected end of path data"),
+            Self::UnexpectedChar(c) => write!(f, "unexpected char {c:?}"),
+            Self::InvalidNumber => f.write_str("invalid number"),
+            Self::InvalidFlag => f.write_str("invalid arc flag"),
+            Self::ExpectedMoveTo => f.write_str("path data must start with a moveto command"),
+            Self::MultipleSubpaths => f.write_str("multiple subpaths are not supported"),
+        }
+    }
+}
+
+impl Display for SvgPathError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at offset {}", self.kind, self.offset)
+    }
+}
+
+impl core::error::Error for SvgPathError {}
+
+struct Lexer<'a> {
+    src: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(src: &'a str) -> Self {
+        Self {
+            src: src.as_bytes(),
+            pos: 0,
+        }
+    }
+
+    fn error(&self, kind: SvgPathErrorKind) -> SvgPathError {
+        SvgPathError::new(kind, self.pos)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.src.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r' | b'\x0C')) {
+            self.pos += 1;
+        }
+    }
+
+    fn skip_separator(&mut self) {
+        self.skip_whitespace();
+        if self.peek() == Some(b',') {
+            self.pos += 1;
+            self.skip_whitespace();
+        }
+    }
+
+    fn at_number(&mut self) -> bool {
+        self.skip_whitespace();
+        matches!(self.peek(), Some(b'0'..=b'9' | b'.' | b'+' | b'-'))
+    }
+
+    fn skip_digits(&mut self) -> usize {
+        let start = self.pos;
+        while matches!(self.peek(), Some(b'0'..=b'9')) {
+            self.pos += 1;
+        }
+        self.pos - start
+    }
+
+    fn number(&mut self) -> Result<f64, SvgPathError> {
+        self.skip_separator();
+
+        let start = self.pos;
+
+        match self.peek() {
+            None => return Err(self.error(SvgPathErrorKind::UnexpectedEnd)),
+            Some(b'+' | b'-') => self.pos += 1,
+            _ => (),
+        }
+
+        let mut digits = self.skip_digits();
+        if self.peek() == Some(b'.') {
+            self.pos += 1;
+            digits += self.skip_digits();
+        }
+
+        if digits == 0 {
+            self.pos = start;
+            return Err(match self.peek() {
+                None => self.error(SvgPathErrorKind::UnexpectedEnd),
+                Some(b'+' | b'-' | b'.') => self.error(SvgPathErrorKind::InvalidNumber),
+                Some(c) => self.error(SvgPathErrorKind::UnexpectedChar(c as char)),
+            });
+        }
+
+        // exponent is only consumed when followed by digits
+        if matches!(self.peek(), Some(b'e' | b'E')) {
+            let mark = self.pos;
+            self.pos += 1;
+            if matches!(self.peek(), Some(b'+' | b'-')) {
+                self.pos += 1;
+            }
+            if self.skip_digits() == 0 {
+                self.pos = mark;
+            }
+        }
+
+        // the slice only contains ASCII chars checked above
+        let s = core::str::from_utf8(&self.src[start..self.pos]).unwrap();
+        s.parse()
+            .map_err(|_| SvgPathError::new(SvgPathErrorKind::InvalidNumber, start))
+    }
+
+    fn flag(&mut self) -> Result<bool, SvgPathError> {
+        self.skip_separator();
+
+        let result = match self.peek() {
+            None => return Err(self.error(SvgPathErrorKind::UnexpectedEnd)),
+            Some(b'0') => false,
+            Some(b'1') => true,
+            Some(_) => return Err(self.error(SvgPathErrorKind::InvalidFlag)),
+        };
+
+        self.pos += 1;
+        Ok(result)
+    }
+
+    fn point<P: Point2D>(&mut self) -> Result<P, SvgPathError> {
+        let x = self.number()?;
+        let y = self.number()?;
+        Ok(P::from_xy(x, y))
+    }
+}
+
+// Last ctrl point used for reflection in S/T command.
+enum LastCtrl<P> {
+    None,
+    Cubic(P),
+    Quad(P),
+}
+
+struct Builder<P> {
+    painter: ShapePainter<P>,
+    // finished subpaths, with the offset in input where each one starts
+    subpaths: Vec<(usize, Shape<P>)>,
+    // offset of the subpath being painted
+    offset: usize,
+    current: P,
+    last_ctrl: LastCtrl<P>,
+}
+
+impl<P: Point2D> Builder<P> {
+    fn new() -> Self {
+        Self {
+            painter: ShapePainter::new(),
+            subpaths: Vec::new(),
+            offset: 0,
+            current: P::from_xy(0.0, 0.0),
+            last_ctrl: LastCtrl::None,
+        }
+    }
+
+    fn resolve(&self, p: P, relative: bool) -> P {
+        if relative {
+            p.plus(&self.current)
+        } else {
+            p
+        }
+    }
+
+    fn reflect(&self, ctrl: &P) -> P {
+        self.current.scale(2.0).minus(ctrl)
+    }
+
+    // Start a new subpath, the current one is finished unless it has only the start point.
+    fn move_to(&mut self, p: P, offset: usize) {
+        if self.painter.closed() || self.painter.len() > 1 {
+            let painter = core::mem::take(&mut self.painter);
+            self.subpaths.push((self.offset, painter.finish()));
+        }
+
+        self.painter.move_to(p.clone());
+        self.offset = offset;
+        self.current = p;
+        self.last_ctrl = LastCtrl::None;
+    }
+
+    fn line_to(&mut self, p: P) {
+        self.painter.line_to(p.clone());
+        self.current = p;
+        self.last_ctrl = LastCtrl::None;
+    }
+
+    fn cubic_to(&mut self, ctrl1: P, ctrl2: P, p: P) {
+        self.painter.cubic_to(ctrl1, ctrl2.clone(), p.clone());
+        self.current = p;
+        self.last_ctrl = LastCtrl::Cubic(ctrl2);
+    }
+
+    fn quad_to(&mut self, ctrl: P, p: P) {
+        self.painter.quad_to(ctrl.clone(), p.clone());
+        self.current = p;
+        self.last_ctrl = LastCtrl::Quad(ctrl);
+    }
+
+    fn arc_to(&mut self, radii: P, rotation: f64, large_arc: bool, sweep: bool, p: P) {
+        // arc with same endpoints is omitted
+        if self.current.distance(&p) == 0.0 {
+            self.last_ctrl = LastCtrl::None;
+            return;
+        }
+
+        // arc with zero radius is a line
+        if Arc::from_endpoints(&self.current, &radii, rotation, large_arc, sweep, &p).is_none() {
+            self.line_to(p);
+            return;
+        }
+
+        self.painter
+            .arc_to(radii, rotation, large_arc, sweep, p.clone());
+        self.current = p;
+        self.last_ctrl = LastCtrl::None;
+    }
+
+    fn close(&mut self) {
+        self.painter.close();
+        self.last_ctrl = LastCtrl::None;
+
+        if let Some(p) = self.painter.current() {
+            self.current = p.clone();
+        }
+    }
+
+    fn finish(mut self) -> Vec<(usize, Shape<P>)> {
+        if !self.painter.is_empty() {
+            self.subpaths.push((self.offset, self.painter.finish()));
+        }
+
+        self.subpaths
+    }
+}
+
+// Parse SVG path data into subpaths, each one with the offset in input where it starts.
+fn parse_subpaths<P: Point2D>(data: &str) -> Result<Vec<(usize, Shape<P>)>, SvgPathError> {
+    let mut lexer = Lexer::new(data);
+    let mut builder = Builder::<P>::new();
+    let mut command: Option<u8> = None;
+
+    loop {
+        lexer.skip_whitespace();
+
+        let offset = lexer.pos;
+        let c = match lexer.peek() {
+            None => break,
+            Some(c) if c.is_ascii_alphabetic() => {
+                lexer.pos += 1;
+                c
+            }
+            Some(c) => match command {
+                // implicit repeat command, moveto becomes lineto
+                Some(last) if lexer.at_number() && !matches!(last, b'Z' | b'z') => match last {
+                    b'M' => b'L',
+                    b'm' => b'l',
+                    last => last,
+                },
+                _ => {
+                    return Err(SvgPathError::new(
+                        SvgPathErrorKind::UnexpectedChar(c as char),
+                        offset,
+                    ));
+                }
+            },
+        };
+
+        if command.is_none() && !matches!(c, b'M' | b'm') {
+            return Err(SvgPathError::new(SvgPathErrorKind::ExpectedMoveTo, offset));
+        }
+
+        // a command after close path starts a new subpath at the start point of last one
+        if builder.painter.closed() && !matches!(c, b'Z' | b'z' | b'M' | b'm') {
+            builder.move_to(builder.current.clone(), offset);
+        }
+
+        let relative = c.is_ascii_lowercase();
+
+        match c.to_ascii_uppercase() {
+            b'M' => {
+                let p: P = lexer.point()?;
+                builder.move_to(builder.resolve(p, relative), offset);
+            }
+            b'L' => {
+                let p: P = lexer.point()?;
+                builder.line_to(builder.resolve(p, relative));
+            }
+            b'H' => {
+                let x = lexer.number()?;
+                let x = if relative { builder.current.x() + x } else { x };
+                builder.line_to(P::from_xy(x, builder.current.y()));
+            }
+            b'V' => {
+                let y = lexer.number()?;
+                let y = if relative { builder.current.y() + y } else { y };
+                builder.line_to(P::from_xy(builder.current.x(), y));
+            }
+            b'C' => {
+                let ctrl1 = builder.resolve(lexer.point()?, relative);
+                let ctrl2 = builder.resolve(lexer.point()?, relative);
+                let p = builder.resolve(lexer.point()?, relative);
+                builder.cubic_to(ctrl1, ctrl2, p);
+            }
+            b'S' => {
+                let ctrl1 = match &builder.last_ctrl {
+                    LastCtrl::Cubic(c) => builder.reflect(c),
+                    _ => builder.current.clone(),
+                };
+                let ctrl2 = builder.resolve(lexer.point()?, relative);
+                let p = builder.resolve(lexer.point()?, relative);
+                builder.cubic_to(ctrl1, ctrl2, p);
+            }
+            b'Q' => {
+                let ctrl = builder.resolve(lexer.point()?, relative);
+                let p = builder.resolve(lexer.point()?, relative);
+                builder.quad_to(ctrl, p);
+            }
+            b'T' => {
+                let ctrl = match &builder.last_ctrl {
+                    LastCtrl::Quad(c) => builder.reflect(c),
+                    _ => builder.current.clone(),
+                };
+                let p = builder.resolve(lexer.point()?, relative);
+                builder.quad_to(ctrl, p);
+            }
+            b'A' => {
+                let radii: P = lexer.point()?;
+                let rotation = lexer.number()?;
+                let large_arc = lexer.flag()?;
+                let sweep = lexer.flag()?;
+                let p = builder.resolve(lexer.point()?, relative);
+                builder.arc_to(radii, rotation, large_arc, sweep, p);
+            }
+            b'Z' => builder.close(),
+            _ => {
+                return Err(SvgPathError::new(
+                    SvgPathErrorKind::UnexpectedChar(c as char),
+                    offset,
+                ));
+            }
+        }
+
+        command.replace(c);
+
+        lexer.skip_separator();
+    }
+
+    Ok(builder.finish())
+}
+
+impl<P: Point2D> Shape<P> {
+    /// Parse SVG path data (the `d` attribute of `<path>` element) into a [Shape].
+    ///
+    /// All commands (M/L/H/V/C/S/Q/T/A/Z) are supported, in both absolute and relative form. Arcs
+    /// are kept exactly as out arc of their start point, see [ArcParams](crate::ArcParams), or
+    /// become line segments if either radius is zero. Collinear control points become
+    /// [SmoothPoint].
+    ///
+    /// A [Shape] has only one contour, so path data contains more then one subpath is an error,
+    /// use [Path::parse_svg_path] for it.
+    ///
+    /// [SmoothPoint]: crate::SmoothPoint
+    pub fn parse_svg_path(data: &str) -> Result<Self, SvgPathError> {
+        let mut subpaths = parse_subpaths(data)?;
+
+        if let Some((offset, _)) = subpaths.get(1) {
+            return Err(SvgPathError::new(
+                SvgPathErrorKind::MultipleSubpaths,
+                *offset,
+            ));
+        }
+
+        Ok(subpaths
+            .pop()
+            .map_or_else(|| ShapePainter::new().finish(), |(_, s)| s))
+    }
+}
+
+impl<P: Point2D> Path<P> {
+    /// Parse SVG path data (the `d` attribute of `<path>` element) into a [Path].
+    ///
+    /// Each moveto command starts a new subpath, so does any command after a close path command.
+    /// See [Shape::parse_svg_path] for details of commands.
+    pub fn parse_svg_path(data: &str) -> Result<Self, SvgPathError> {
+        Ok(parse_subpaths(data)?.into_iter().map(|(_, s)| s).collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Curve, CurvePoint};
+
+    fn parse(data: &str) -> Shape<(f64, f64)> {
+        Shape::parse_svg_path(data).unwrap()
+    }
+
+    fn error(data: &str) -> SvgPathError {
+        Shape::<(f64, f64)>::parse_svg_path(data).err().unwrap()
+    }
+
+    #[test]
+    fn svg_parse_lines() {
+        let shape = parse("M 10,20 L 30 40 h 10 v-5 H 0 V 0 z");
+        let points: Vec<_> = shape.points().iter().map(|p| *p.point()).collect();
+        assert_eq!(points, [
+            (10.0, 20.0),
+            (30.0, 40.0),
+            (40.0, 40.0),
+            (40.0, 35.0),
+            (0.0, 35.0),
+            (0.0, 0.0)
+        ]);
+        assert!(shape.closed());
+        assert!(shape.curves().all(|c| matches!(c, Curve::Segment(_))));
+    }
+
+    #[test]
+    fn svg_parse_implicit_lineto() {
+        let shape = parse("m1 1 2 2 3-3");
+        let points: Vec<_> = shape.points().iter().map(|p| *p.point()).collect();
+        assert_eq!(points, [(1.0, 1.0), (3.0, 3.0), (6.0, 0.0)]);
+        assert!(!shape.closed());
+    }
+
+    #[test]
+    fn svg_parse_compact_numbers() {
+        let shape = parse("M.5.5L-1e1-.5");
+        let points: Vec<_> = shape.points().iter().map(|p| *p.point()).collect();
+        assert_eq!(points, [(0.5, 0.5), (-10.0, -0.5)]);
+    }
+
+    #[test]
+    fn svg_parse_smooth_cubic() {
+        let shape = parse("M0 0 C 0 10 10 10 10 0 S 20 -10 20 0");
+        assert_eq!(shape.len(), 3);
+        assert!(matches!(shape.points()[0], CurvePoint::Corner(_)));
+        assert!(matches!(shape.points()[1], CurvePoint::Smooth(_)));
+        assert!(matches!(shape.points()[2], CurvePoint::Corner(_)));
+    }
+
+    #[test]
+    fn svg_parse_quad() {
+        let shape = parse("M0 0 Q 5 10 10 0 T 20 0");
+        assert_eq!(shape.len(), 3);
+        let out_ctrl = shape.points()[1].out_ctrl().unwrap();
+        assert_eq!(*out_ctrl, (15.0, -10.0));
+        assert!(shape.points()[1].in_ctrl().is_none());
+    }
+
+    #[test]
+    fn svg_parse_arc() {
+        let shape = parse("M 0 0 A 10 10 0 1 1 0 20 A 10 10 0 1 1 0 0 Z");
+        assert!(shape.closed());
+        assert_eq!(shape.len(), 2);
+        for c in shape.curves() {
+            assert!(matches!(c, Curve::Arc(_)));
+            for i in 0..=10 {
+                let p = c.at(i as f64 / 10.0);
+                let r = p.distance(&(0.0, 10.0));
+                assert!((r - 10.0).abs() < 1e-12, "{r}");
+            }
+        }
+
+        // relative, and zero radius is a line
+        let shape = parse("M 0 0 a 5 5 0 0 0 10 0 A 0 5 0 0 0 20 0");
+        let arc = shape.points()[0].out_arc().unwrap();
+        assert_eq!(arc.radii, (5.0, 5.0));
+        assert!(!arc.sweep);
+        assert_eq!(shape.points()[1].point(), &(10.0, 0.0));
+        assert!(matches!(shape.curves().nth(1), Some(Curve::Segment(_))));
+    }
+
+    #[test]
+    fn svg_parse_close_merge_last_point() {
+        let shape = parse("M0 0 L 10 0 C 10 10 0 10 0 0 Z");
+        assert_eq!(shape.len(), 2);
+        assert_eq!(*shape.points()[0].in_ctrl().unwrap(), (0.0, 10.0));
+    }
+
+    #[test]
+    fn svg_parse_empty() {
+        assert!(parse("  ").is_empty());
+    }
+
+    #[test]
+    fn svg_parse_errors() {
+        assert_eq!(error("L 0 0"), SvgPathError {
+            kind: SvgPathErrorKind::ExpectedMoveTo,
+            offset: 0
+        });
+        assert_eq!(error("M 0 0 L 1"), SvgPathError {
+            kind: SvgPathErrorKind::UnexpectedEnd,
+            offset: 9
+        });
+        assert_eq!(error("M 0 0 L 1 x"), SvgPathError {
+            kind: SvgPathErrorKind::UnexpectedChar('x'),
+            offset: 10
+        });
+        assert_eq!(error("M 0 0 A 1 1 0 2 0 1 1"), SvgPathError {
+            kind: SvgPathErrorKind::InvalidFlag,
+            offset: 14
+        });
+        assert_eq!(error("M 0 0 L 1 1 M 2 2 L 3 3"), SvgPathError {
+            kind: SvgPathErrorKind::MultipleSubpaths,
+            offset: 12
+        });
+        assert_eq!(error("M 0 0 L 1 1 Z L 3 3"), SvgPathError {
+            kind: SvgPathErrorKind::MultipleSubpaths,
+            offset: 14
+        });
+    }
+
+    #[test]
+    fn svg_parse_subpaths() {
+        let path = Path::<(f64, f64)>::parse_svg_path("M 0 0 L 1 1 M 2 2 L 3 3").unwrap();
+        assert_eq!(path.len(), 2);
+        assert_eq!(*path.subpaths()[1].points()[0].point(), (2.0, 2.0));
+
+        // command after close path starts from the start point of last subpath
+        let path = Path::<(f64, f64)>::parse_svg_path("M 5 5 l 1 0 l 0 1 z l -1 0 z").unwrap();
+        assert_eq!(path.len(), 2);
+        let points: Vec<_> = path.subpaths()[1]
+            .points()
+            .iter()
+            .map(|p| *p.point())
+            .collect();
+        assert_eq!(points, [(5.0, 5.0), (4.0, 5.0)]);
+        assert!(path.subpaths()[1].closed());
+
+        // single moveto is replaced
+        let path = Path::<(f64, f64)>::parse_svg_path("M 0 0 M 1 1 L 2 2").unwrap();
+        assert_eq!(path.len(), 1);
+    }
+}
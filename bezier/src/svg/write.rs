@@ -0,0 +1,351 @@
+use alloc::string::String;
+use core::fmt::Write;
+
+use crate::{ArcParams, Curve, Path, Point2D, Shape};
+
+/// Options of SVG path data output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SvgPathFormat {
+    precision: Option<usize>,
+    relative: bool,
+}
+
+/// Builder
+impl SvgPathFormat {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Max digits after decimal point, trailing zeros are removed.
+    ///
+    /// By default, numbers are written in the shortest form that can be parsed back exactly.
+    pub fn with_precision(mut self, precision: usize) -> Self {
+        self.precision.replace(precision);
+        self
+    }
+
+    /// Use relative commands (lowercase) instead of absolute ones.
+    ///
+    /// Without precision, a command is still written as absolute one if any of its points can't
+    /// be written as a delta which is parsed back exactly.
+    pub fn with_relative(mut self, relative: bool) -> Self {
+        self.relative = relative;
+        self
+    }
+}
+
+/// Getter
+impl SvgPathFormat {
+    pub fn precision(&self) -> Option<usize> {
+        self.precision
+    }
+
+    pub fn relative(&self) -> bool {
+        self.relative
+    }
+}
+
+// Delta from `from` to `to` which gives back exactly `to` when added to `from`, as parsers do.
+// The rounded difference may miss it by an ulp, and there may be none if their magnitudes are far
+// apart.
+fn delta(from: f64, to: f64) -> Option<f64> {
+    let d = to - from;
+    [d, d.next_up(), d.next_down()]
+        .into_iter()
+        .find(|d| from + d == to)
+}
+
+struct Writer<'a> {
+    out: String,
+    format: &'a SvgPathFormat,
+    // current point, already rounded to precision
+    current: (f64, f64),
+    // start point of current subpath, where close path command goes back to
+    start: (f64, f64),
+}
+
+impl<'a> Writer<'a> {
+    fn new(format: &'a SvgPathFormat) -> Self {
+        Self {
+            out: String::new(),
+            format,
+            current: (0.0, 0.0),
+            start: (0.0, 0.0),
+        }
+    }
+
+    fn round(&self, v: f64) -> f64 {
+        match self.format.precision {
+            Some(p) => {
+                let scale = libm::pow(10.0, p as f64);
+                libm::round(v * scale) / scale
+            }
+            None => v,
+        }
+    }
+
+    fn number(&mut self, v: f64) {
+        // avoid "-0"
+        let v = if v == 0.0 { 0.0 } else { v };
+
+        match self.format.precision {
+            Some(p) => {
+                let start = self.out.len();
+                write!(self.out, "{v:.p$}").unwrap();
+                if self.out[start..].contains('.') {
+                    let trimmed = self.out.trim_end_matches('0').trim_end_matches('.').len();
+                    self.out.truncate(trimmed);
+                }
+                if &self.out[start..] == "-0" {
+                    self.out.replace_range(start.., "0");
+                }
+            }
+            None => write!(self.out, "{v}").unwrap(),
+        }
+    }
+
+    // Write command `c` with its points, and return the last rounded point. Other arguments are
+    // written by `args` before the points.
+    //
+    // A relative command is written as absolute one if any point can't be written as an exact
+    // delta, see `delta`.
+    fn command<P: Point2D, const N: usize>(
+        &mut self, c: char, points: [&P; N], args: impl FnOnce(&mut Self),
+    ) -> (f64, f64) {
+        let rounded = points.map(|p| (self.round(p.x()), self.round(p.y())));
+        let (cx, cy) = self.current;
+        let deltas = rounded.map(|(x, y)| match self.format.precision {
+            Some(_) => Some((x - cx, y - cy)),
+            None => Some((delta(cx, x)?, delta(cy, y)?)),
+        });
+        let relative = self.format.relative && deltas.iter().all(Option::is_some);
+
+        if !self.out.is_empty() {
+            self.out.push(' ');
+        }
+        self.out
+            .push(if relative { c.to_ascii_lowercase() } else { c });
+
+        let start = self.out.len();
+        args(self);
+
+        for (i, (p, d)) in rounded.iter().zip(deltas).enumerate() {
+            if i > 0 || self.out.len() > start {
+                self.out.push(' ');
+            }
+
+            let (x, y) = match d {
+                Some(d) if relative => d,
+                _ => *p,
+            };
+            self.number(x);
+            self.out.push(' ');
+            self.number(y);
+        }
+
+        rounded[N - 1]
+    }
+
+    fn move_to<P: Point2D>(&mut self, p: &P) {
+        self.current = self.command('M', [p], |_| ());
+        self.start = self.current;
+    }
+
+    fn line_to<P: Point2D>(&mut self, p: &P) {
+        self.current = self.command('L', [p], |_| ());
+    }
+
+    fn cubic_to<P: Point2D>(&mut self, ctrl1: &P, ctrl2: &P, p: &P) {
+        self.current = self.command('C', [ctrl1, ctrl2, p], |_| ());
+    }
+
+    fn quad_to<P: Point2D>(&mut self, ctrl: &P, p: &P) {
+        self.current = self.command('Q', [ctrl, p], |_| ());
+    }
+
+    fn flag(&mut self, v: bool) {
+        self.out.push(' ');
+        self.out.push(if v { '1' } else { '0' });
+    }
+
+    fn arc_to<P: Point2D>(&mut self, arc: &ArcParams<P>, p: &P) {
+        self.current = self.command('A', [p], |w| {
+            w.number(arc.radii.x());
+            w.out.push(' ');
+            w.number(arc.radii.y());
+            w.out.push(' ');
+            w.number(arc.rotation);
+            w.flag(arc.large_arc);
+            w.flag(arc.sweep);
+        });
+    }
+
+    fn curve<P: Point2D>(&mut self, curve: &Curve<P>) {
+        match curve {
+            Curve::Segment(s) => self.line_to(s.end()),
+            Curve::Quad(q) => self.quad_to(&q.ctrl, &q.end),
+            Curve::Bezier(b) => self.cubic_to(&b.ctrl1, &b.ctrl2, &b.end),
+            Curve::Arc(a) => self.arc_to(&a.params(), &a.end()),
+        }
+    }
+
+    fn close(&mut self) {
+        if !self.out.is_empty() {
+            self.out.push(' ');
+        }
+        self.out.push(if self.format.relative { 'z' } else { 'Z' });
+        self.current = self.start;
+    }
+
+    fn shape<P: Point2D>(&mut self, shape: &Shape<P>) {
+        let Some(first) = shape.points().first() else {
+            return;
+        };
+
+        self.move_to(first.point());
+
+        let points = shape.points();
+        let count = shape.len() - 1;
+        for (i, curve) in shape.curves().enumerate() {
+            // close path command draws the line back to start point itself
+            if i == count && matches!(curve, Curve::Segment(_)) {
+                break;
+            }
+
+            // arc is written as given, instead of the one resolved from it
+            match (&curve, points[i].out_arc()) {
+                (Curve::Arc(_), Some(arc)) => {
+                    self.arc_to(arc, points[(i + 1) % points.len()].point())
+                }
+                _ => self.curve(&curve),
+            }
+        }
+
+        if shape.closed() {
+            self.close();
+        }
+    }
+}
+
+impl<P: Point2D> Shape<P> {
+    /// Write the shape as SVG path data, which can be used as `d` attribute of `<path>` element.
+    ///
+    /// [Segment](crate::Segment) is written as `L` command, [QuadBezier](crate::QuadBezier) as
+    /// `Q` command, [Bezier](crate::Bezier) as `C` command, and [Arc](crate::Arc) as `A` command.
+    /// If the shape is closed, a `Z` command is added at the end.
+    pub fn to_svg_path(&self, format: &SvgPathFormat) -> String {
+        let mut writer = Writer::new(format);
+        writer.shape(self);
+        writer.out
+    }
+}
+
+impl<P: Point2D> Path<P> {
+    /// Write the path as SVG path data, each subpath starts with a `M` command.
+    ///
+    /// See [Shape::to_svg_path] for details of commands.
+    pub fn to_svg_path(&self, format: &SvgPathFormat) -> String {
+        let mut writer = Writer::new(format);
+        for shape in self {
+            writer.shape(shape);
+        }
+        writer.out
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn svg_write_lines() {
+        let shape = Shape::<(f64, f64)>::parse_svg_path("M0 0 L10 0 L10 10 Z").unwrap();
+        let format = SvgPathFormat::new();
+        assert_eq!(shape.to_svg_path(&format), "M0 0 L10 0 L10 10 Z");
+        let format = format.with_relative(true);
+        assert_eq!(shape.to_svg_path(&format), "m0 0 l10 0 l0 10 z");
+    }
+
+    #[test]
+    fn svg_write_curves() {
+        let data = "M0 0 C0 10 10 10 10 0 C10 -10 0 -10 0 0 Z";
+        let shape = Shape::<(f64, f64)>::parse_svg_path(data).unwrap();
+        assert_eq!(shape.len(), 2);
+        assert_eq!(
+            shape.to_svg_path(&SvgPathFormat::new().with_precision(3)),
+            data
+        );
+    }
+
+    #[test]
+    fn svg_write_quad_round_trip() {
+        let shape = Shape::<(f64, f64)>::parse_svg_path("M0 0 Q 5 10 10 0 T 20 0").unwrap();
+        assert_eq!(
+            shape.to_svg_path(&SvgPathFormat::new()),
+            "M0 0 Q5 10 10 0 Q15 -10 20 0"
+        );
+        assert_eq!(
+            shape.to_svg_path(&SvgPathFormat::new().with_relative(true)),
+            "m0 0 q5 10 10 0 q5 -10 10 0"
+        );
+    }
+
+    #[test]
+    fn svg_write_arc_round_trip() {
+        let data = "M0 0 A5 5 0 0 1 10 0 A10 5 30 1 0 0 0 Z";
+        let shape = Shape::<(f64, f64)>::parse_svg_path(data).unwrap();
+        assert_eq!(shape.to_svg_path(&SvgPathFormat::new()), data);
+        assert_eq!(
+            shape.to_svg_path(&SvgPathFormat::new().with_relative(true)),
+            "m0 0 a5 5 0 0 1 10 0 a10 5 30 1 0 -10 0 z"
+        );
+    }
+
+    #[test]
+    fn svg_write_precision() {
+        let shape = Shape::<(f64, f64)>::parse_svg_path("M0.12345 -0.0001 L1.5 2.25").unwrap();
+        let format = SvgPathFormat::new().with_precision(2);
+        assert_eq!(shape.to_svg_path(&format), "M0.12 0 L1.5 2.25");
+        let format = format.with_relative(true);
+        assert_eq!(shape.to_svg_path(&format), "m0.12 0 l1.38 2.25");
+    }
+
+    #[test]
+    fn svg_write_relative_round_trip() {
+        let data = "M0.1 0.7 L0.3 0.2 L1.7 0.9 Z";
+        let shape = Shape::<(f64, f64)>::parse_svg_path(data).unwrap();
+        let relative = shape.to_svg_path(&SvgPathFormat::new().with_relative(true));
+
+        // 0.2 plus any float is never 0.9, so the last line is absolute
+        assert_eq!(
+            relative,
+            "m0.1 0.7 l0.19999999999999998 -0.49999999999999994 L1.7 0.9 z"
+        );
+        let parsed = Shape::<(f64, f64)>::parse_svg_path(&relative).unwrap();
+        assert_eq!(parsed.to_svg_path(&SvgPathFormat::new()), data);
+    }
+
+    #[test]
+    fn svg_write_empty() {
+        let shape = Shape::<(f64, f64)>::default();
+        assert_eq!(shape.to_svg_path(&SvgPathFormat::new()), "");
+    }
+
+    #[test]
+    fn svg_path_round_trip() {
+        let data = "M0 0 L10 0 L10 10 L0 10 Z M2 2 L2 8 L8 8 Z M20 0 C20 10 30 10 30 0";
+        let path = Path::<(f64, f64)>::parse_svg_path(data).unwrap();
+        assert_eq!(path.len(), 3);
+        assert!(path.subpaths()[1].closed());
+        assert!(!path.subpaths()[2].closed());
+        assert_eq!(path.to_svg_path(&SvgPathFormat::new()), data);
+
+        let relative = path.to_svg_path(&SvgPathFormat::new().with_relative(true));
+        assert_eq!(
+            relative,
+            "m0 0 l10 0 l0 10 l-10 0 z m2 2 l0 6 l6 0 z m18 -2 c0 10 10 10 10 0"
+        );
+        let parsed = Path::<(f64, f64)>::parse_svg_path(&relative).unwrap();
+        assert_eq!(parsed.to_svg_path(&SvgPathFormat::new()), data);
+    }
+}
@@ -0,0 +1,91 @@
+use crate::Point2D;
+
+/// 2D affine transform, in the same form as SVG `matrix(a b c d e f)`:
+///
+/// ```text
+/// x' = a * x + c * y + e
+/// y' = b * x + d * y + f
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub d: f64,
+    pub e: f64,
+    pub f: f64,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Transform {
+    pub fn new(a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) -> Self {
+        Self { a, b, c, d, e, f }
+    }
+
+    pub fn identity() -> Self {
+        Self::new(1.0, 0.0, 0.0, 1.0, 0.0, 0.0)
+    }
+
+    pub fn translate(tx: f64, ty: f64) -> Self {
+        Self::new(1.0, 0.0, 0.0, 1.0, tx, ty)
+    }
+
+    pub fn scale(sx: f64, sy: f64) -> Self {
+        Self::new(sx, 0.0, 0.0, sy, 0.0, 0.0)
+    }
+
+    /// Rotation around the origin, angle is in degree, positive from x axis to y axis.
+    pub fn rotate(angle: f64) -> Self {
+        let (sin, cos) = libm::sincos(angle.to_radians());
+
+        Self::new(cos, sin, -sin, cos, 0.0, 0.0)
+    }
+
+    /// Transform which applies `self` first, then `next`.
+    pub fn then(&self, next: &Self) -> Self {
+        Self::new(
+            next.a * self.a + next.c * self.b,
+            next.b * self.a + next.d * self.b,
+            next.a * self.c + next.c * self.d,
+            next.b * self.c + next.d * self.d,
+            next.a * self.e + next.c * self.f + next.e,
+            next.b * self.e + next.d * self.f + next.f,
+        )
+    }
+
+    pub fn apply<P: Point2D>(&self, p: &P) -> P {
+        P::from_xy(
+            self.a * p.x() + self.c * p.y() + self.e,
+            self.b * p.x() + self.d * p.y() + self.f,
+        )
+    }
+
+    /// Upper bound of how much a length may be stretched by the transform.
+    pub fn max_scale(&self) -> f64 {
+        // frobenius norm is never less than the spectral norm
+        libm::sqrt(self.a * self.a + self.b * self.b + self.c * self.c + self.d * self.d)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn transform_compose() {
+        let t = Transform::scale(2.0, 3.0)
+            .then(&Transform::rotate(90.0))
+            .then(&Transform::translate(1.0, 1.0));
+
+        let p = t.apply(&(1.0, 1.0));
+        assert!(p.distance(&(-2.0, 3.0)) < 1e-12);
+
+        assert_eq!(Transform::default().apply(&(4.0, 5.0)), (4.0, 5.0));
+        assert!(t.max_scale() >= 3.0);
+    }
+}